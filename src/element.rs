@@ -1,21 +1,51 @@
 mod blockbox;
 pub use blockbox::*;
 
+mod columnbox;
+pub use columnbox::*;
+
+mod field;
+pub use field::*;
+
 mod filling;
 pub use filling::*;
 
+mod footnote;
+pub use footnote::*;
+
+mod gridbox;
+pub use gridbox::*;
+
 mod layoutbox;
 pub use layoutbox::*;
 
+mod marker;
+pub use marker::*;
+
 mod page_break;
 pub use page_break::*;
 
+mod section;
+pub use section::*;
+
+mod stackbox;
+pub use stackbox::*;
+
 mod style;
 pub use style::*;
 
+mod table;
+pub use table::*;
+
+mod target;
+pub use target::*;
+
 mod text;
 pub use text::*;
 
+mod transform;
+pub use transform::*;
+
 mod wrap;
 pub use wrap::*;
 
@@ -1,18 +1,117 @@
 use std::sync::Arc;
 
 use crate::{
-    AlignItems, Axis, Error, Layout, MeasureContext, Position, RenderContext, Style, Styled,
+    AlignItems, Axis, Error, Footnote, Layout, MeasureContext, Position, RenderContext, Style,
+    Styled,
     dimension::{Dim, MaybeDim},
+    layout::resolve_intrinsic,
     position::{Offset, Size},
     unit::{Fill, Unit, sub_unit},
 };
 
+// Placement of a BlockBox child resolved after measurement. Insets refer to edges of the box content,
+// a child anchored on both opposite edges stretches between them. Children without any anchor
+// in an axis keep their own offset. Children are painted in order of z-index.
+#[derive(Debug, Default, Clone)]
+pub struct Anchor {
+    top: Option<Unit>,
+    left: Option<Unit>,
+    bottom: Option<Unit>,
+    right: Option<Unit>,
+    center_horizontally: bool,
+    center_vertically: bool,
+    z_index: i32,
+}
+
+impl Anchor {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn top(mut self, top: impl Into<Unit>) -> Self {
+        self.top = Some(top.into());
+        self
+    }
+
+    pub fn left(mut self, left: impl Into<Unit>) -> Self {
+        self.left = Some(left.into());
+        self
+    }
+
+    pub fn bottom(mut self, bottom: impl Into<Unit>) -> Self {
+        self.bottom = Some(bottom.into());
+        self
+    }
+
+    pub fn right(mut self, right: impl Into<Unit>) -> Self {
+        self.right = Some(right.into());
+        self
+    }
+
+    pub fn center_horizontally(mut self) -> Self {
+        self.center_horizontally = true;
+        self
+    }
+
+    pub fn center_vertically(mut self) -> Self {
+        self.center_vertically = true;
+        self
+    }
+
+    pub fn center(self) -> Self {
+        self.center_horizontally().center_vertically()
+    }
+
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    // stretched children get their own size fixed between the anchored edges
+    fn place(&self, offset: &Offset, room: &Size, measured: &Size, size: &mut Size) -> Offset {
+        let resolve =
+            |start: Option<Unit>, end: Option<Unit>, center, own, room, size| match (start, end) {
+                (Some(start), Some(end)) => (start, Some(room - start - end)),
+                (Some(start), None) => (start, None),
+                (None, Some(end)) => (room - end - size, None),
+                (None, None) if center => ((room - size) * 0.5, None),
+                (None, None) => (own, None),
+            };
+
+        let (x, width) = resolve(
+            self.left,
+            self.right,
+            self.center_horizontally,
+            offset.x,
+            room.base_width(),
+            measured.base_width(),
+        );
+        let (y, height) = resolve(
+            self.top,
+            self.bottom,
+            self.center_vertically,
+            offset.y,
+            room.base_height(),
+            measured.base_height(),
+        );
+
+        if let Some(width) = width {
+            size.set_base_width(width);
+        }
+        if let Some(height) = height {
+            size.set_base_height(height);
+        }
+        Offset::new(x, y)
+    }
+}
+
 pub struct BlockBox {
     mark: Option<&'static str>,
     offset: Offset,
     size: Size,
     style: Arc<Style>,
     children: Vec<Box<dyn Layout>>,
+    anchors: Vec<Anchor>,
 }
 
 impl BlockBox {
@@ -23,6 +122,7 @@ impl BlockBox {
             size: Size::content(),
             style: Style::new(),
             children: vec![],
+            anchors: vec![],
         }
     }
     pub fn mark(mut self, mark: &'static str) -> Self {
@@ -56,7 +156,7 @@ impl BlockBox {
     }
 
     pub fn axis_depth(mut self, depth: impl Into<Unit>) -> Self {
-        if !matches!(self.style.align_items(), AlignItems::Baseline) {
+        if !self.style.align_items().is_baseline() {
             tracing::warn!("Depth set for a box having items not aligned on a baseline");
         }
         self.size.set_depth(Some(depth));
@@ -129,11 +229,20 @@ impl BlockBox {
         }))
     }
 
-    pub fn child_dyn(mut self, mut child: Box<dyn Layout>) -> Self {
+    pub fn child_dyn(self, child: Box<dyn Layout>) -> Self {
+        self.child_anchored_dyn(Anchor::new(), child)
+    }
+
+    pub fn child_anchored(self, anchor: Anchor, child: impl Layout + 'static) -> Self {
+        self.child_anchored_dyn(anchor, Box::new(child))
+    }
+
+    pub fn child_anchored_dyn(mut self, anchor: Anchor, mut child: Box<dyn Layout>) -> Self {
         let style = child.style_ref().inherit(self.style_ref());
         child.size_mut().apply_style(Axis::Horizontal, &style);
         child.set_style(style);
         self.children.push(child);
+        self.anchors.push(anchor);
         self
     }
 
@@ -150,9 +259,29 @@ impl BlockBox {
                 child
             })
             .collect::<Vec<_>>();
+        self.anchors.extend(children.iter().map(|_| Anchor::new()));
         self.children.extend(children);
         self
     }
+
+    // Children are positioned absolutely, the intrinsic size reaches their farthest corner.
+    fn intrinsic_size(&mut self, ctx: &mut dyn MeasureContext, min: bool) -> Result<Size, Error> {
+        let mut corner = Offset::zero();
+        for child in self.children.iter_mut() {
+            let size = if min {
+                child.min_content_size(ctx)?
+            } else {
+                child.max_content_size(ctx)?
+            };
+            let child_corner = &child.style_ref().margin().offset(child.offset()) + &size;
+            corner = Offset::new(corner.x.max(child_corner.x), corner.y.max(child_corner.y));
+        }
+
+        let mut size = Size::fixed(corner.x, corner.y);
+        self.style_ref().insets().widen(None, Some(&mut size));
+
+        Ok(self.size.definite_or(&size))
+    }
 }
 
 impl Position for BlockBox {
@@ -194,34 +323,53 @@ impl Styled for BlockBox {
 
 impl Layout for BlockBox {
     fn measure(&mut self, ctx: &mut dyn MeasureContext, mut room: Size) -> Result<(), Error> {
+        // calc expressions refer to parent size and font size
+        let font_size = self.style.font_size(ctx);
+        if let Some(style) = self.style.resolve_padding(room.base_width(), font_size) {
+            self.style = style;
+        }
+
         let axis_room = Axis::Horizontal.base_size(&room);
         Axis::Horizontal
             .dim_mut(self.size_mut())
-            .resolve_parented(axis_room);
+            .resolve_parented(axis_room, font_size);
 
         let cross_room = Axis::Vertical.base_size(&room);
         Axis::Vertical
             .dim_mut(self.size_mut())
-            .resolve_parented(cross_room);
+            .resolve_parented(cross_room, font_size);
+
+        resolve_intrinsic(self, ctx)?;
 
-        let ascent = if self.children.is_empty() {
-            self.size.ascent()
+        self.size.apply_aspect_ratio(&self.style, None);
+
+        let (ascent, last_ascent) = if self.children.is_empty() {
+            (self.size.ascent(), self.size.last_ascent())
         } else {
-            self.style_ref().padding().narrow(None, Some(&mut room));
+            self.style_ref().insets().narrow(None, Some(&mut room));
             for child in self.children.iter_mut() {
                 child.measure(ctx, room.clone())?;
             }
-            self.children
-                .first()
-                .unwrap()
-                .size_after_wrap_ref()
-                .and_then(|size| size.ascent())
+            (
+                self.children
+                    .first()
+                    .unwrap()
+                    .size_after_wrap_ref()
+                    .and_then(|size| size.ascent()),
+                self.children
+                    .last()
+                    .unwrap()
+                    .size_after_wrap_ref()
+                    .and_then(|size| size.last_ascent()),
+            )
         };
 
-        let respect_baseline = matches!(self.style_ref().align_items(), AlignItems::Baseline);
+        let respect_baseline = self.style_ref().align_items().is_baseline();
         if respect_baseline && self.size().depth().is_none() {
             self.size
                 .set_depth(ascent.map(|ascent| self.size.base_height() - ascent));
+            self.size
+                .set_last_depth(last_ascent.map(|ascent| self.size.base_height() - ascent));
         }
 
         Ok(())
@@ -233,45 +381,83 @@ impl Layout for BlockBox {
         mut offset: Offset,
         mut room: Size,
     ) -> Result<(), Error> {
+        self.size.apply_aspect_ratio(&self.style, Some(&room));
+
         // resolve padding
         self.style_ref()
-            .padding()
+            .insets()
             .narrow(Some(&mut offset), Some(&mut room));
 
         // Resolve relative positioning of request and self ascents, when aligning to baseline.
         let align_items = self.style_ref().align_items();
-        let self_ascent = self.size_after_wrap_ref().and_then(|size| size.ascent());
+        let self_ascent = self
+            .size_after_wrap_ref()
+            .and_then(|size| match align_items {
+                AlignItems::LastBaseline => size.last_ascent(),
+                _ => size.ascent(),
+            });
         if matches!(
             (align_items, room.depth(), self_ascent),
-            (AlignItems::Baseline, Some(_), Some(_))
+            (
+                AlignItems::Baseline | AlignItems::LastBaseline,
+                Some(_),
+                Some(_)
+            )
         ) {
             let self_to_parent_ascent = sub_unit(room.ascent(), self_ascent);
             offset.y_advance(self_to_parent_ascent.unwrap_or_default());
         }
 
-        let ascent = if self.children.is_empty() {
-            self.size.ascent()
+        let (ascent, last_ascent) = if self.children.is_empty() {
+            (self.size.ascent(), self.size.last_ascent())
         } else {
-            for child in self.children.iter_mut() {
-                let child_offset = child.offset() + &offset;
-                child.lay_out(ctx, child_offset, child.size().clone())?;
+            let mut content = self.size.clone();
+            self.style_ref().insets().narrow(None, Some(&mut content));
+
+            for (child, anchor) in self.children.iter_mut().zip(&self.anchors) {
+                // children are positioned absolutely, margins just move them
+                let measured = child
+                    .size_after_wrap_ref()
+                    .unwrap_or_else(|| child.size())
+                    .clone();
+                let own_offset = child.offset().clone();
+                let child_offset = anchor.place(&own_offset, &content, &measured, child.size_mut());
+                let child_offset = child.style_ref().margin().offset(&(child_offset + &offset));
+                let child_size = child.size().clone();
+                child.lay_out(ctx, child_offset, child_size)?;
             }
-            self.children
-                .first()
-                .unwrap()
-                .size_after_wrap_ref()
-                .and_then(|size| size.ascent())
+
+            // the last baseline is the one of the last child where it is placed
+            let last = self.children.last().unwrap();
+            let last_ascent = last
+                .size_after_lay_out()
+                .and_then(|size| size.last_ascent())
+                .map(|ascent| last.offset().y - offset.y + ascent);
+            (
+                self.children
+                    .first()
+                    .unwrap()
+                    .size_after_wrap_ref()
+                    .and_then(|size| size.ascent()),
+                last_ascent,
+            )
         };
 
         // Adopt final offset and size including padding
-        self.style_ref().padding().widen(Some(&mut offset), None);
+        self.style_ref().insets().widen(Some(&mut offset), None);
         self.offset = offset;
 
-        let respect_baseline = matches!(self.style_ref().align_items(), AlignItems::Baseline);
+        let respect_baseline = align_items.is_baseline();
         if respect_baseline && self.size().depth().is_none() {
             self.size
                 .set_depth(ascent.map(|ascent| self.size.base_height() - ascent));
         }
+        if respect_baseline && !self.children.is_empty() {
+            let last_ascent =
+                last_ascent.map(|ascent| self.style_ref().insets().top_size() + ascent);
+            self.size
+                .set_last_depth(last_ascent.map(|ascent| self.size.base_height() - ascent));
+        }
 
         Ok(())
     }
@@ -279,41 +465,15 @@ impl Layout for BlockBox {
     fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
         ctx.check_page_break(self.offset.y, self.size.height.base_size(), true);
 
-        for child in self.iter() {
-            child.render(ctx)?;
-        }
-
-        let style = self.style_ref();
-        let top_left = self.offset();
-        let bottom_right = top_left + &self.size;
-
-        if let Some(stroke) = style.border_top() {
-            ctx.line(
-                &self.offset,
-                &Offset::new(bottom_right.x, top_left.y),
-                stroke,
-            );
-        }
-
-        if let Some(stroke) = style.border_right() {
-            ctx.line(
-                &Offset::new(bottom_right.x, top_left.y),
-                &bottom_right,
-                stroke,
-            );
-        }
-
-        if let Some(stroke) = style.border_bottom() {
-            ctx.line(
-                &bottom_right,
-                &Offset::new(top_left.x, bottom_right.y),
-                stroke,
-            );
+        // paint order by z-index, stable for equal ones
+        let mut order = (0..self.children.len()).collect::<Vec<_>>();
+        order.sort_by_key(|index| self.anchors[*index].z_index);
+        for index in order {
+            self.children[index].render(ctx)?;
         }
 
-        if let Some(stroke) = style.border_left() {
-            ctx.line(&Offset::new(top_left.x, bottom_right.y), top_left, stroke);
-        }
+        self.style_ref()
+            .render_border(ctx, self.offset(), self.size());
 
         ctx.debug_frame(self.offset(), self.size());
 
@@ -322,6 +482,21 @@ impl Layout for BlockBox {
         Ok(())
     }
 
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, true)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, false)
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.children
+            .iter_mut()
+            .flat_map(|child| child.footnotes_mut())
+            .collect()
+    }
+
     fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
         Box::new(self.children.iter())
     }
@@ -0,0 +1,537 @@
+use std::sync::Arc;
+
+use crate::{
+    Axis, Error, Footnote, Layout, MeasureContext, Position, RenderContext, Stroke, Style,
+    StyleBuilder, Styled,
+    dimension::{Dim, MaybeDim},
+    position::{Affine, Offset, Size},
+    unit::{Fill, Unit},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Columns {
+    Count(usize),
+    // as many columns as fit, at least of the width
+    Width(Unit),
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnFill {
+    // columns of the same height, as low as possible
+    #[default]
+    Balance,
+    // each column filled up to the box height before the next one
+    Sequential,
+}
+
+// Part of a child in a column, from and to are heights within the child.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnFragment {
+    pub child: usize,
+    pub column: usize,
+    pub from: Unit,
+    pub to: Unit,
+    pub offset: Offset,
+}
+
+// Children flow from the bottom of one column to the top of the next one.
+// They break between each other, or inside at their breaks (e.g. between lines of paragraphs).
+// Children stay whole, a child broken inside is laid out at its first fragment and the other
+// fragments render it moved into their columns and clipped.
+pub struct ColumnBox {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    columns: Columns,
+    rule: Option<Stroke>,
+    fill: ColumnFill,
+    children: Vec<Box<dyn Layout>>,
+    column_height: Option<Unit>,
+    column_tracks: Vec<(Unit, Unit)>,
+    fragments: Vec<ColumnFragment>,
+}
+
+impl ColumnBox {
+    pub fn new() -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::none(),
+            style: StyleBuilder::new().build(),
+            columns: Columns::Count(1),
+            rule: None,
+            fill: ColumnFill::default(),
+            children: vec![],
+            column_height: None,
+            column_tracks: vec![],
+            fragments: vec![],
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn width(mut self, size: impl Into<Dim>) -> Self {
+        self.size.width.set_base(size);
+        self
+    }
+
+    pub fn min_width(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.width.set_min(size);
+        self
+    }
+
+    pub fn max_width(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.width.set_max(size);
+        self
+    }
+
+    pub fn height(mut self, size: impl Into<Dim>) -> Self {
+        self.size.height.set_base(size);
+        self
+    }
+
+    pub fn min_height(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.height.set_min(size);
+        self
+    }
+
+    pub fn max_height(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.height.set_max(size);
+        self
+    }
+
+    pub fn column_count(mut self, count: usize) -> Self {
+        self.columns = Columns::Count(count.max(1));
+        self
+    }
+
+    pub fn column_width(mut self, width: impl Into<Unit>) -> Self {
+        self.columns = Columns::Width(width.into());
+        self
+    }
+
+    pub fn rule(mut self, stroke: Stroke) -> Self {
+        self.rule = Some(stroke);
+        self
+    }
+
+    pub fn fill(mut self, fill: ColumnFill) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(&self.style));
+        self
+    }
+
+    pub fn child(self, child: impl Layout + 'static) -> Self {
+        self.child_dyn(Box::new(child))
+    }
+
+    pub fn child_dyn(mut self, mut child: Box<dyn Layout>) -> Self {
+        let style = child.style_ref().inherit(self.style_ref());
+        child.size_mut().apply_style(Axis::Horizontal, &style);
+        child.set_style(style);
+        self.children.push(child);
+        self
+    }
+
+    pub fn children<L, IL, IIL>(self, children: IIL) -> Self
+    where
+        IIL: IntoIterator<Item = IL>,
+        IL: Into<L>,
+        L: Layout + 'static,
+    {
+        children.into_iter().fold(self, |columns, child| {
+            let child: Box<dyn Layout> = Box::new(child.into());
+            columns.child_dyn(child)
+        })
+    }
+
+    // Offsets and widths of columns taken by children after lay out.
+    pub fn column_tracks(&self) -> &[(Unit, Unit)] {
+        &self.column_tracks
+    }
+
+    // Parts of children in columns after lay out, in flow order.
+    pub fn fragments(&self) -> &[ColumnFragment] {
+        &self.fragments
+    }
+
+    // Count and width of columns fitting into the content width.
+    fn resolve_columns(&self, width: Unit) -> (usize, Unit) {
+        let gap = self.style_ref().horizontal_gap_size();
+        let count = match self.columns {
+            Columns::Count(count) => count.max(1),
+            Columns::Width(column) => ((width + gap) / (column + gap).max(Unit(1))).max(1),
+        };
+        let gaps = gap * (count - 1);
+        (count, (width - gaps) * (Fill(1), Fill(count)))
+    }
+
+    // Columns as wide as the widest child, as high as children spread evenly over the columns.
+    // Columns of a width are intrinsically a single one.
+    fn intrinsic_size(&mut self, ctx: &mut dyn MeasureContext, min: bool) -> Result<Size, Error> {
+        let (mut widest, mut tallest, mut height) = (Unit::zero(), Unit::zero(), Unit::zero());
+        for child in self.children.iter_mut() {
+            let size = if min {
+                child.min_content_size(ctx)?
+            } else {
+                child.max_content_size(ctx)?
+            };
+            widest = widest.max(size.base_width());
+            tallest = tallest.max(size.base_height());
+            height += size.base_height();
+        }
+
+        let (count, column_width) = match self.columns {
+            Columns::Count(count) => (count.max(1), widest),
+            Columns::Width(width) => (1, widest.max(width)),
+        };
+        let gaps = self.style_ref().horizontal_gap_size() * (count - 1);
+        let mut size = Size::fixed(
+            column_width * count + gaps,
+            tallest.max(height * (Fill(1), Fill(count))),
+        );
+        self.style_ref().insets().widen(None, Some(&mut size));
+
+        Ok(self.size.definite_or(&size))
+    }
+}
+
+impl Default for ColumnBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Number of columns the pieces take, when columns are of the height.
+// Pieces are given by their height and breaks.
+fn columns_taken(pieces: &[(Unit, Vec<Unit>)], height: Unit) -> usize {
+    let mut columns = 1;
+    let mut used = Unit::zero();
+
+    for (size, breaks) in pieces {
+        let mut start = Unit::zero();
+        while *size - start > height - used {
+            let fitting = breaks
+                .iter()
+                .filter(|at| **at > start && **at - start <= height - used)
+                .max();
+            match fitting {
+                Some(at) => start = *at,
+                // taller than a whole column, it overflows
+                None if used.is_zero() => break,
+                None => (),
+            }
+            columns += 1;
+            used = Unit::zero();
+        }
+        used += *size - start;
+    }
+
+    columns
+}
+
+// The lowest column height, which the pieces fit into the count of columns with.
+fn balanced_height(pieces: &[(Unit, Vec<Unit>)], count: usize) -> Unit {
+    let mut low = Unit::zero();
+    let mut high = pieces
+        .iter()
+        .fold(Unit::zero(), |sum, (size, _)| sum + *size);
+
+    while low < high {
+        let middle = Unit((low.0 + high.0) / 2);
+        if columns_taken(pieces, middle) <= count {
+            high = middle;
+        } else {
+            low = middle + Unit(1);
+        }
+    }
+
+    high
+}
+
+impl Position for ColumnBox {
+    fn element(&self) -> &str {
+        "ColumnBox"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &self.size
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+}
+
+impl Styled for ColumnBox {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.size.apply_style(Axis::Horizontal, &style);
+        self.style = style;
+    }
+}
+
+impl Layout for ColumnBox {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, mut room: Size) -> Result<(), Error> {
+        // calc expressions refer to parent size and font size
+        let font_size = self.style.font_size(ctx);
+        if let Some(style) = self.style.resolve_padding(room.base_width(), font_size) {
+            self.style = style;
+        }
+
+        self.size
+            .width
+            .resolve_parented(room.base_width(), font_size);
+        self.size
+            .height
+            .resolve_parented(room.base_height(), font_size);
+
+        let mut self_size = self.size.clone();
+        self.style_ref().insets().narrow(None, Some(&mut self_size));
+        self.style_ref().insets().narrow(None, Some(&mut room));
+
+        let width = self_size.width.size_available(room.base_width());
+        let (count, column_width) = self.resolve_columns(width);
+
+        // children are laid out into a column once to find their heights and breaks
+        let mut pieces = Vec::with_capacity(self.children.len());
+        for child in self.children.iter_mut() {
+            child.measure(ctx, Size::fixed(column_width, room.base_height()))?;
+            let height = child
+                .size_after_wrap_ref()
+                .map(|size| size.base_height())
+                .unwrap_or_default();
+            child.lay_out(ctx, Offset::zero(), Size::fixed(column_width, height))?;
+            let height = child
+                .size_after_lay_out()
+                .map(|size| size.base_height())
+                .unwrap_or_default();
+            pieces.push((height, child.breaks()));
+        }
+
+        let column_height = match self.fill {
+            ColumnFill::Balance => balanced_height(&pieces, count),
+            ColumnFill::Sequential if self_size.height.is_resolved() => {
+                self_size.height.base_size()
+            }
+            ColumnFill::Sequential => room.base_height(),
+        };
+        self.column_height = Some(column_height);
+
+        let mut content_size = Size::fixed(width, column_height);
+        self.style_ref()
+            .insets()
+            .widen(None, Some(&mut content_size));
+
+        self.size.width.resolve_content(content_size.base_width());
+        self.size.height.resolve_content(content_size.base_height());
+
+        Ok(())
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, true)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, false)
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        mut offset: Offset,
+        mut room: Size,
+    ) -> Result<(), Error> {
+        // resolve padding
+        self.style_ref()
+            .insets()
+            .narrow(Some(&mut offset), Some(&mut room));
+
+        let mut size = self.size.clone();
+        self.style_ref().insets().narrow(None, Some(&mut size));
+
+        let width = size.width.size_available(room.base_width());
+        let (count, column_width) = self.resolve_columns(width);
+        let column_height = self.column_height.unwrap_or_else(|| size.base_height());
+
+        // children flow into columns, the ones crossing column bottom are broken or moved
+        let gap = self.style_ref().horizontal_gap_size();
+        let place = |column: usize, used: Unit| {
+            Offset::new(offset.x + (column_width + gap) * column, offset.y + used)
+        };
+        let mut fragments = vec![];
+        let mut column = 0;
+        let mut used = Unit::zero();
+        let mut tallest = Unit::zero();
+
+        for (index, child) in self.children.iter_mut().enumerate() {
+            let child_offset = place(column, used);
+            child.measure(ctx, Size::fixed(column_width, column_height))?;
+            let height = child
+                .size_after_wrap_ref()
+                .map(|size| size.base_height())
+                .unwrap_or_default();
+            child.lay_out(ctx, child_offset.clone(), Size::fixed(column_width, height))?;
+            let height = child
+                .size_after_lay_out()
+                .map(|size| size.base_height())
+                .unwrap_or_default();
+            let breaks = child.breaks();
+
+            let first = fragments.len();
+            let mut start = Unit::zero();
+            loop {
+                let left = column_height - used;
+                let fitting = breaks
+                    .iter()
+                    .filter(|at| **at > start && **at - start <= left)
+                    .max()
+                    .copied();
+                let end = match fitting {
+                    // the rest fits, or overflows the last column or a whole column
+                    _ if height - start <= left || column + 1 >= count => height,
+                    None if used.is_zero() => height,
+                    end => end.unwrap_or(start),
+                };
+
+                if end > start || height.is_zero() {
+                    fragments.push(ColumnFragment {
+                        child: index,
+                        column,
+                        from: start,
+                        to: end,
+                        offset: place(column, used),
+                    });
+                    used += end - start;
+                    tallest = tallest.max(used);
+                    start = end;
+                }
+                if start >= height {
+                    break;
+                }
+
+                column += 1;
+                used = Unit::zero();
+            }
+
+            // the child moved to the next column as a whole
+            if fragments[first].offset != child_offset {
+                child.lay_out(
+                    ctx,
+                    fragments[first].offset.clone(),
+                    Size::fixed(column_width, height),
+                )?;
+            }
+        }
+        self.fragments = fragments;
+
+        self.column_tracks = (0..=column.min(count - 1))
+            .map(|column| (place(column, Unit::zero()).x, column_width))
+            .collect();
+        self.column_height = Some(column_height.max(tallest));
+
+        let content_size = Size::fixed(width, column_height.max(tallest));
+        Axis::Horizontal.resolve_content_size(&mut size, &content_size, room.base_width());
+        Axis::Vertical.resolve_content_size(&mut size, &content_size, room.base_height());
+
+        // Adopt final offset and size including padding
+        self.style_ref()
+            .insets()
+            .widen(Some(&mut offset), Some(&mut size));
+
+        self.offset = offset;
+        self.size = size;
+
+        Ok(())
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        for (index, child) in self.children.iter().enumerate() {
+            let mut fragments = self
+                .fragments
+                .iter()
+                .filter(|fragment| fragment.child == index)
+                .peekable();
+            let first = fragments.next();
+            if fragments.peek().is_none() {
+                child.render(ctx)?;
+                continue;
+            }
+
+            for fragment in first.into_iter().chain(fragments) {
+                let width = self.column_tracks[fragment.column].1;
+                let height = fragment.to - fragment.from;
+                let by = Affine::translation(
+                    fragment.offset.x - child.offset().x,
+                    fragment.offset.y - child.offset().y - fragment.from,
+                );
+                ctx.push_clip(&fragment.offset, &Size::fixed(width, height));
+                ctx.push_transform(&by);
+                child.render(ctx)?;
+                ctx.pop_transform();
+                ctx.pop_clip();
+            }
+        }
+
+        // rules in the middle of gaps between columns taken
+        if let (Some(stroke), Some(column_height)) = (&self.rule, self.column_height) {
+            let top = self.offset.y + self.style_ref().insets().top_size();
+            let gap = self.style_ref().horizontal_gap_size();
+            for (column_offset, _) in self.column_tracks.iter().skip(1) {
+                let x = *column_offset - gap * 0.5;
+                ctx.line(
+                    &Offset::new(x, top),
+                    &Offset::new(x, top + column_height),
+                    stroke,
+                );
+            }
+        }
+
+        self.style_ref()
+            .render_border(ctx, self.offset(), self.size());
+
+        ctx.debug_frame(self.offset(), self.size());
+
+        Ok(())
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.children
+            .iter_mut()
+            .flat_map(|child| child.footnotes_mut())
+            .collect()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
+        Box::new(self.children.iter())
+    }
+}
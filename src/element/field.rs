@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use crate::{
+    Error, Layout, MeasureContext, Position, RenderContext, Style, Styled, Text,
+    position::{Offset, Size},
+};
+
+// Value known only after pagination, provided by the measure context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    PageNumber,
+    PageCount,
+    SectionTitle,
+    // Page number and label of the target with the id, see Target.
+    TargetPage(String),
+    TargetLabel(String),
+    // Text of the first or last marker with the name on the page, see Marker.
+    Marker(String, MarkerOnPage),
+}
+
+// Marker of a page a field shows. Without any marker on the page, the last one before it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerOnPage {
+    First,
+    Last,
+}
+
+// Text of a field value, resolved whenever measured.
+pub struct Field {
+    mark: Option<&'static str>,
+    kind: FieldKind,
+    text: Text,
+}
+
+impl Field {
+    pub fn new(kind: FieldKind) -> Self {
+        Self {
+            mark: None,
+            kind,
+            text: Text::new(""),
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn kind(&self) -> &FieldKind {
+        &self.kind
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(self.style_ref()));
+        self
+    }
+}
+
+impl Position for Field {
+    fn element(&self) -> &str {
+        "Field"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        self.text.offset()
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        self.text.offset_mut()
+    }
+
+    fn size(&self) -> &Size {
+        self.text.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.text.size_mut()
+    }
+}
+
+impl Styled for Field {
+    fn style_ref(&self) -> &Style {
+        self.text.style_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.text.set_style(style);
+    }
+}
+
+impl Layout for Field {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, room: Size) -> Result<(), Error> {
+        let value = ctx.field(&self.kind).unwrap_or_default();
+        self.text = Text::new(value).style(Arc::new(self.text.style_ref().clone()));
+        self.text.measure(ctx, room)
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        offset: Offset,
+        size: Size,
+    ) -> Result<(), Error> {
+        self.text.lay_out(ctx, offset, size)
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        self.text.render(ctx)
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.measure(ctx, Size::none())?;
+        self.text.min_content_size(ctx)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.measure(ctx, Size::none())?;
+        self.text.max_content_size(ctx)
+    }
+}
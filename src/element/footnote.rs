@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use crate::{
+    Error, Layout, MeasureContext, Position, RenderContext, Style, Styled, Text,
+    position::{Offset, Size},
+};
+
+// Reference mark in the flow, its body goes to the footnote area of the page the mark is on.
+pub struct Footnote {
+    mark: Option<&'static str>,
+    reference: Text,
+    body: Option<Box<dyn Layout>>,
+}
+
+impl Footnote {
+    pub fn new(reference: impl ToString, body: impl Layout + 'static) -> Self {
+        Self {
+            mark: None,
+            reference: Text::new(reference),
+            body: Some(Box::new(body)),
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(self.style_ref()));
+        self
+    }
+
+    // None, once pagination moved the body to the footnote area.
+    pub fn body(&self) -> Option<&dyn Layout> {
+        self.body.as_deref()
+    }
+
+    pub fn body_mut(&mut self) -> Option<&mut Box<dyn Layout>> {
+        self.body.as_mut()
+    }
+
+    pub fn take_body(&mut self) -> Option<Box<dyn Layout>> {
+        self.body.take()
+    }
+}
+
+impl Position for Footnote {
+    fn element(&self) -> &str {
+        "Footnote"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        self.reference.offset()
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        self.reference.offset_mut()
+    }
+
+    fn size(&self) -> &Size {
+        self.reference.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.reference.size_mut()
+    }
+}
+
+impl Styled for Footnote {
+    fn style_ref(&self) -> &Style {
+        self.reference.style_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.reference.set_style(style);
+    }
+}
+
+impl Layout for Footnote {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, room: Size) -> Result<(), Error> {
+        self.reference.measure(ctx, room)
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        offset: Offset,
+        size: Size,
+    ) -> Result<(), Error> {
+        self.reference.lay_out(ctx, offset, size)
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        self.reference.render(ctx)
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.reference.min_content_size(ctx)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.reference.max_content_size(ctx)
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        vec![self]
+    }
+}
@@ -0,0 +1,762 @@
+use std::sync::Arc;
+
+use crate::{
+    Axis, Error, Footnote, Layout, MeasureContext, Position, RenderContext, Style, StyleBuilder,
+    Styled,
+    dimension::{Dim, MaybeDim},
+    position::{Offset, Size},
+    unit::{Fill, FillPerMille, Unit},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Track {
+    Fixed(Unit),
+    Parent(FillPerMille),
+    Fraction(Fill),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+impl Track {
+    pub const fn auto() -> Self {
+        Self::Auto
+    }
+
+    pub const fn min_content() -> Self {
+        Self::MinContent
+    }
+
+    pub const fn max_content() -> Self {
+        Self::MaxContent
+    }
+
+    pub fn fixed(size: impl Into<Unit>) -> Self {
+        Self::Fixed(size.into())
+    }
+
+    pub fn parent(fill: impl Into<FillPerMille>) -> Self {
+        Self::Parent(fill.into())
+    }
+
+    pub fn fr(fill: impl Into<Fill>) -> Self {
+        Self::Fraction(fill.into())
+    }
+}
+
+impl<IU> From<IU> for Track
+where
+    IU: Into<Unit>,
+{
+    fn from(unit: IU) -> Self {
+        Self::Fixed(unit.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridArea {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl GridArea {
+    pub const fn new(row: usize, column: usize) -> Self {
+        Self {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    pub fn with_span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span.max(1);
+        self.column_span = column_span.max(1);
+        self
+    }
+
+    pub const fn row_end(&self) -> usize {
+        self.row + self.row_span
+    }
+
+    pub const fn column_end(&self) -> usize {
+        self.column + self.column_span
+    }
+
+    pub const fn contains(&self, row: usize, column: usize) -> bool {
+        row >= self.row
+            && row < self.row_end()
+            && column >= self.column
+            && column < self.column_end()
+    }
+
+    pub const fn span(&self, axis: Axis) -> (usize, usize) {
+        match axis {
+            Axis::Horizontal => (self.column, self.column_span),
+            Axis::Vertical => (self.row, self.row_span),
+        }
+    }
+}
+
+pub struct GridBox {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    children: Vec<Box<dyn Layout>>,
+    areas: Vec<GridArea>,
+    cursor: usize,
+    content_size: Option<Size>,
+    column_tracks: Vec<(Unit, Unit)>,
+    row_tracks: Vec<(Unit, Unit)>,
+    // min-content and max-content sizes of children, if in tracks sized by them
+    intrinsic_sizes: Vec<(Option<Size>, Option<Size>)>,
+}
+
+impl GridBox {
+    pub fn new() -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::none(),
+            style: StyleBuilder::new().build(),
+            columns: vec![],
+            rows: vec![],
+            children: vec![],
+            areas: vec![],
+            cursor: 0,
+            content_size: None,
+            column_tracks: vec![],
+            row_tracks: vec![],
+            intrinsic_sizes: vec![],
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn width(mut self, size: impl Into<Dim>) -> Self {
+        self.size.width.set_base(size);
+        self
+    }
+
+    pub fn min_width(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.width.set_min(size);
+        self
+    }
+
+    pub fn max_width(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.width.set_max(size);
+        self
+    }
+
+    pub fn height(mut self, size: impl Into<Dim>) -> Self {
+        self.size.height.set_base(size);
+        self
+    }
+
+    pub fn min_height(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.height.set_min(size);
+        self
+    }
+
+    pub fn max_height(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.height.set_max(size);
+        self
+    }
+
+    pub fn column(mut self, track: impl Into<Track>) -> Self {
+        self.columns.push(track.into());
+        self
+    }
+
+    pub fn columns<IT>(mut self, tracks: impl IntoIterator<Item = IT>) -> Self
+    where
+        IT: Into<Track>,
+    {
+        self.columns.extend(tracks.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn row(mut self, track: impl Into<Track>) -> Self {
+        self.rows.push(track.into());
+        self
+    }
+
+    pub fn rows<IT>(mut self, tracks: impl IntoIterator<Item = IT>) -> Self
+    where
+        IT: Into<Track>,
+    {
+        self.rows.extend(tracks.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(&self.style));
+        self
+    }
+
+    pub fn child(self, child: impl Layout + 'static) -> Self {
+        self.child_dyn(Box::new(child))
+    }
+
+    pub fn child_at(self, row: usize, column: usize, child: impl Layout + 'static) -> Self {
+        self.child_area_dyn(GridArea::new(row, column), Box::new(child))
+    }
+
+    pub fn child_span(
+        self,
+        row: usize,
+        column: usize,
+        row_span: usize,
+        column_span: usize,
+        child: impl Layout + 'static,
+    ) -> Self {
+        self.child_area_dyn(
+            GridArea::new(row, column).with_span(row_span, column_span),
+            Box::new(child),
+        )
+    }
+
+    pub fn children<L, IL, IIL>(self, children: IIL) -> Self
+    where
+        IIL: IntoIterator<Item = IL>,
+        IL: Into<L>,
+        L: Layout + 'static,
+    {
+        children.into_iter().fold(self, |grid, child| {
+            let child: Box<dyn Layout> = Box::new(child.into());
+            grid.child_dyn(child)
+        })
+    }
+
+    // Places child into the next cell not occupied yet, row by row.
+    pub fn child_dyn(mut self, child: Box<dyn Layout>) -> Self {
+        let columns = self.columns.len().max(1);
+        let mut index = self.cursor;
+        while self
+            .areas
+            .iter()
+            .any(|area| area.contains(index / columns, index % columns))
+        {
+            index += 1;
+        }
+        self.cursor = index + 1;
+        self.child_area_dyn(GridArea::new(index / columns, index % columns), child)
+    }
+
+    pub fn child_area_dyn(mut self, area: GridArea, mut child: Box<dyn Layout>) -> Self {
+        child.set_style(child.style_ref().inherit(self.style_ref()));
+        self.children.push(child);
+        self.areas.push(area);
+        self
+    }
+
+    pub fn areas(&self) -> &[GridArea] {
+        &self.areas
+    }
+
+    // Offsets and sizes of columns after lay out.
+    pub fn column_tracks(&self) -> &[(Unit, Unit)] {
+        &self.column_tracks
+    }
+
+    // Offsets and sizes of rows after lay out.
+    pub fn row_tracks(&self) -> &[(Unit, Unit)] {
+        &self.row_tracks
+    }
+
+    pub(crate) fn insert_rows(&mut self, at: usize, count: usize) {
+        self.areas
+            .iter_mut()
+            .filter(|area| area.row >= at)
+            .for_each(|area| area.row += count);
+        if at < self.rows.len() {
+            self.rows
+                .splice(at..at, std::iter::repeat_n(Track::Auto, count));
+        }
+    }
+
+    // Moves children from the row on into a new grid of the same columns and style,
+    // the rows staying need to be measured and laid out again.
+    pub(crate) fn split_rows_off(&mut self, row: usize) -> GridBox {
+        let mut tail = GridBox::new();
+        tail.mark = self.mark;
+        tail.size = self.size.clone();
+        tail.style = self.style.clone();
+        tail.columns = self.columns.clone();
+        if row < self.rows.len() {
+            tail.rows = self.rows.split_off(row);
+        }
+
+        let children = std::mem::take(&mut self.children);
+        let areas = std::mem::take(&mut self.areas);
+        for (child, mut area) in children.into_iter().zip(areas) {
+            if area.row < row {
+                self.children.push(child);
+                self.areas.push(area);
+            } else {
+                area.row -= row;
+                tail.children.push(child);
+                tail.areas.push(area);
+            }
+        }
+
+        for grid in [&mut *self, &mut tail] {
+            grid.content_size = None;
+            grid.row_tracks.clear();
+            grid.intrinsic_sizes.clear();
+            grid.size.set_base_height(Dim::content());
+        }
+        tail
+    }
+
+    fn track_count(&self, axis: Axis) -> usize {
+        let defined = match axis {
+            Axis::Horizontal => self.columns.len(),
+            Axis::Vertical => self.rows.len(),
+        };
+        self.areas
+            .iter()
+            .map(|area| {
+                let (start, span) = area.span(axis);
+                start + span
+            })
+            .fold(defined, usize::max)
+    }
+
+    fn tracks(&self, axis: Axis) -> &[Track] {
+        match axis {
+            Axis::Horizontal => &self.columns,
+            Axis::Vertical => &self.rows,
+        }
+    }
+
+    // Whether the child is alone in a track sized by min-content or max-content of children.
+    fn in_track(&self, area: &GridArea, track: Track) -> bool {
+        [Axis::Horizontal, Axis::Vertical].into_iter().any(|axis| {
+            let (start, span) = area.span(axis);
+            span == 1 && self.tracks(axis).get(start) == Some(&track)
+        })
+    }
+
+    // Sizes of children along the axis, min-content or max-content ones in tracks sized by them.
+    fn child_sizes(&self, axis: Axis) -> Vec<Option<Unit>> {
+        self.children
+            .iter()
+            .zip(&self.areas)
+            .enumerate()
+            .map(|(index, (child, area))| {
+                let size = child.size_after_wrap_ref()?;
+                let (start, span) = area.span(axis);
+                let intrinsic = self.intrinsic_sizes.get(index);
+                let size = match (span, self.tracks(axis).get(start), intrinsic) {
+                    (1, Some(Track::MinContent), Some((Some(min), _))) => min,
+                    (1, Some(Track::MaxContent), Some((_, Some(max)))) => max,
+                    _ => size,
+                };
+                Some(axis.base_size(size))
+            })
+            .collect()
+    }
+
+    // Tracks sized by intrinsic sizes of children, the ones relative to room as if content sized.
+    fn intrinsic_size(&mut self, ctx: &mut dyn MeasureContext, min: bool) -> Result<Size, Error> {
+        let mut sizes = Vec::with_capacity(self.children.len());
+        for child in self.children.iter_mut() {
+            sizes.push(if min {
+                child.min_content_size(ctx)?
+            } else {
+                child.max_content_size(ctx)?
+            });
+        }
+
+        let horizontal_gap = self.style_ref().horizontal_gap_size();
+        let vertical_gap = self.style_ref().vertical_gap_size();
+        let columns = resolve_tracks(
+            Axis::Horizontal,
+            &self.columns,
+            self.track_count(Axis::Horizontal),
+            Unit::zero(),
+            false,
+            horizontal_gap,
+            &sizes
+                .iter()
+                .map(|size| Some(size.base_width()))
+                .collect::<Vec<_>>(),
+            &self.areas,
+        );
+        let rows = resolve_tracks(
+            Axis::Vertical,
+            &self.rows,
+            self.track_count(Axis::Vertical),
+            Unit::zero(),
+            false,
+            vertical_gap,
+            &sizes
+                .iter()
+                .map(|size| Some(size.base_height()))
+                .collect::<Vec<_>>(),
+            &self.areas,
+        );
+
+        let mut size = Size::fixed(
+            span_size(&columns, 0, columns.len(), horizontal_gap),
+            span_size(&rows, 0, rows.len(), vertical_gap),
+        );
+        self.style_ref().insets().widen(None, Some(&mut size));
+
+        Ok(self.size.definite_or(&size))
+    }
+
+    // Size is the own size narrowed by padding, room is the room narrowed by padding.
+    fn resolve_tracks(&self, size: &Size, room: &Size) -> (Vec<Unit>, Vec<Unit>) {
+        let columns = resolve_tracks(
+            Axis::Horizontal,
+            &self.columns,
+            self.track_count(Axis::Horizontal),
+            size.width.size_available(room.base_width()),
+            true,
+            self.style_ref().horizontal_gap_size(),
+            &self.child_sizes(Axis::Horizontal),
+            &self.areas,
+        );
+
+        // As in CSS, rows of a content-sized grid have no definite room to be distributed.
+        let rows = resolve_tracks(
+            Axis::Vertical,
+            &self.rows,
+            self.track_count(Axis::Vertical),
+            size.height.size_available(room.base_height()),
+            !size.height.is_content(),
+            self.style_ref().vertical_gap_size(),
+            &self.child_sizes(Axis::Vertical),
+            &self.areas,
+        );
+
+        (columns, rows)
+    }
+}
+
+impl Default for GridBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn resolve_tracks(
+    axis: Axis,
+    tracks: &[Track],
+    count: usize,
+    room: Unit,
+    definite: bool,
+    gap: Unit,
+    child_sizes: &[Option<Unit>],
+    areas: &[GridArea],
+) -> Vec<Unit> {
+    if count == 0 {
+        return vec![];
+    }
+
+    let tracks = (0..count)
+        .map(
+            |index| match tracks.get(index).copied().unwrap_or(Track::Auto) {
+                Track::Parent(_) | Track::Fraction(_) if !definite => Track::Auto,
+                track => track,
+            },
+        )
+        .collect::<Vec<_>>();
+    let is_content =
+        |track: &Track| matches!(track, Track::Auto | Track::MinContent | Track::MaxContent);
+
+    let mut sizes = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fixed(size) => *size,
+            Track::Parent(fill) => room * (*fill, FillPerMille::mille()),
+            _ => Unit::zero(),
+        })
+        .collect::<Vec<_>>();
+
+    let child_sizes = child_sizes
+        .iter()
+        .zip(areas)
+        .filter_map(|(size, area)| size.map(|size| (area.span(axis), size)))
+        .collect::<Vec<_>>();
+
+    // Content tracks take the size of the biggest single-track child.
+    for ((start, _), child_size) in child_sizes.iter().filter(|((_, span), _)| *span == 1) {
+        if is_content(&tracks[*start]) {
+            sizes[*start] = sizes[*start].max(*child_size);
+        }
+    }
+
+    // Spanning children enlarge content tracks they span over evenly.
+    for ((start, span), child_size) in child_sizes.iter().filter(|((_, span), _)| *span > 1) {
+        let spanned = *start..(*start + *span).min(count);
+        let covered = span_size(&sizes, *start, *span, gap);
+        let content_tracks = tracks[spanned.clone()]
+            .iter()
+            .filter(|t| is_content(t))
+            .count();
+        if *child_size > covered && content_tracks > 0 {
+            let deficit = *child_size - covered;
+            let share = deficit * (Fill::equal(), Fill::new(content_tracks));
+            let mut rest = deficit;
+            let spanned = spanned
+                .filter(|index| is_content(&tracks[*index]))
+                .collect::<Vec<_>>();
+            for (position, index) in spanned.iter().enumerate() {
+                // the last track takes the remainder of rounded shares
+                let add = if position + 1 == spanned.len() {
+                    rest
+                } else {
+                    share.min(rest)
+                };
+                sizes[*index] += add;
+                rest -= add;
+            }
+        }
+    }
+
+    // Fractions share the room left after all other tracks and gaps.
+    let sum_fraction = tracks.iter().fold(Fill::none(), |sum, track| match track {
+        Track::Fraction(fill) => sum + *fill,
+        _ => sum,
+    });
+    if sum_fraction.permille() > 0 {
+        let used = sizes.iter().fold(Unit::zero(), |sum, size| sum + *size);
+        let free = (room - used - gap * (count - 1)).max(Unit::zero());
+        for (size, track) in sizes.iter_mut().zip(&tracks) {
+            if let Track::Fraction(fill) = track {
+                *size = free * (*fill, sum_fraction);
+            }
+        }
+    }
+
+    sizes
+}
+
+fn span_size(sizes: &[Unit], start: usize, span: usize, gap: Unit) -> Unit {
+    let tracks = sizes
+        .iter()
+        .skip(start)
+        .take(span)
+        .fold(Unit::zero(), |sum, size| sum + *size);
+    tracks + gap * span.saturating_sub(1)
+}
+
+fn track_offsets(sizes: &[Unit], gap: Unit) -> Vec<Unit> {
+    sizes
+        .iter()
+        .scan(Unit::zero(), |offset, size| {
+            let current = *offset;
+            *offset += *size + gap;
+            Some(current)
+        })
+        .collect()
+}
+
+impl Position for GridBox {
+    fn element(&self) -> &str {
+        "GridBox"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &self.size
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    fn content_size(&self) -> Option<&Size> {
+        self.content_size.as_ref()
+    }
+}
+
+impl Styled for GridBox {
+    fn style_ref(&self) -> &Style {
+        &self.style
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.children.iter_mut().for_each(|child| {
+            child.set_style(child.style_ref().inherit(&style));
+        });
+        self.size.apply_style(Axis::Horizontal, &style);
+        self.style = style;
+    }
+}
+
+impl Layout for GridBox {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, mut room: Size) -> Result<(), Error> {
+        // calc expressions refer to parent size and font size
+        let font_size = self.style.font_size(ctx);
+        if let Some(style) = self.style.resolve_padding(room.base_width(), font_size) {
+            self.style = style;
+        }
+
+        let width_room = room.base_width();
+        self.size.width.resolve_parented(width_room, font_size);
+        let height_room = room.base_height();
+        self.size.height.resolve_parented(height_room, font_size);
+
+        let mut self_size = self.size.clone();
+        self.style_ref().insets().narrow(None, Some(&mut self_size));
+        self.style_ref().insets().narrow(None, Some(&mut room));
+
+        let mut intrinsic_sizes = Vec::with_capacity(self.children.len());
+        for (index, area) in self.areas.iter().enumerate() {
+            let min = self.in_track(area, Track::MinContent);
+            let max = self.in_track(area, Track::MaxContent);
+            let child = &mut self.children[index];
+            child.measure(ctx, room.clone())?;
+            intrinsic_sizes.push((
+                min.then(|| child.min_content_size(ctx)).transpose()?,
+                max.then(|| child.max_content_size(ctx)).transpose()?,
+            ));
+        }
+        self.intrinsic_sizes = intrinsic_sizes;
+
+        let (columns, rows) = self.resolve_tracks(&self_size, &room);
+        let mut content_size = Size::fixed(
+            span_size(
+                &columns,
+                0,
+                columns.len(),
+                self.style_ref().horizontal_gap_size(),
+            ),
+            span_size(&rows, 0, rows.len(), self.style_ref().vertical_gap_size()),
+        );
+
+        self.style_ref()
+            .insets()
+            .widen(None, Some(&mut content_size));
+
+        self.size.width.resolve_content(content_size.base_width());
+        self.size.height.resolve_content(content_size.base_height());
+
+        Ok(())
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, true)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, false)
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        mut offset: Offset,
+        mut room: Size,
+    ) -> Result<(), Error> {
+        // resolve padding
+        self.style_ref()
+            .insets()
+            .narrow(Some(&mut offset), Some(&mut room));
+
+        let mut size = self.size.clone();
+        self.style_ref().insets().narrow(None, Some(&mut size));
+
+        let horizontal_gap = self.style_ref().horizontal_gap_size();
+        let vertical_gap = self.style_ref().vertical_gap_size();
+
+        let (columns, rows) = self.resolve_tracks(&size, &room);
+        let column_offsets = track_offsets(&columns, horizontal_gap);
+        let row_offsets = track_offsets(&rows, vertical_gap);
+
+        for (child, area) in self.children.iter_mut().zip(&self.areas) {
+            let child_offset = Offset::new(
+                offset.x + column_offsets[area.column],
+                offset.y + row_offsets[area.row],
+            );
+            let child_size = Size::fixed(
+                span_size(&columns, area.column, area.column_span, horizontal_gap),
+                span_size(&rows, area.row, area.row_span, vertical_gap),
+            );
+            child.lay_out(ctx, child_offset, child_size)?;
+        }
+
+        let content_size = Size::fixed(
+            span_size(&columns, 0, columns.len(), horizontal_gap),
+            span_size(&rows, 0, rows.len(), vertical_gap),
+        );
+
+        Axis::Horizontal.resolve_content_size(&mut size, &content_size, room.base_width());
+        Axis::Vertical.resolve_content_size(&mut size, &content_size, room.base_height());
+
+        self.content_size = Some(content_size);
+        self.column_tracks = column_offsets
+            .iter()
+            .zip(&columns)
+            .map(|(column_offset, size)| (offset.x + *column_offset, *size))
+            .collect();
+        self.row_tracks = row_offsets
+            .iter()
+            .zip(&rows)
+            .map(|(row_offset, size)| (offset.y + *row_offset, *size))
+            .collect();
+
+        // Adopt final offset and size including padding
+        self.style_ref()
+            .insets()
+            .widen(Some(&mut offset), Some(&mut size));
+
+        self.offset = offset;
+        self.size = size;
+
+        Ok(())
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        for child in self.iter() {
+            child.render(ctx)?;
+        }
+
+        self.style_ref()
+            .render_border(ctx, self.offset(), self.size());
+
+        ctx.debug_frame(self.offset(), self.size());
+
+        Ok(())
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.children
+            .iter_mut()
+            .flat_map(|child| child.footnotes_mut())
+            .collect()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
+        Box::new(self.children.iter())
+    }
+}
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::{
-    AlignItems, Axis, Error, Layout, MeasureContext, Position, RenderContext, Style, StyleBuilder,
-    Styled,
-    children::lay_out_native,
-    dimension::{Dim, MaybeDim},
-    position::{Offset, Size},
+    AlignItems, Axis, BreakBetween, BreakInside, Error, Float, Footnote, Layout, MeasureContext,
+    Overflow, Position, RenderContext, Style, StyleBuilder, Styled,
+    children::{effective_margins, lay_out_native_with_margins},
+    dimension::{Dim, FlexDim, MaybeDim, distribute},
+    layout::resolve_intrinsic,
+    position::{Affine, Indent, Offset, Size},
     unit::{Fill, Unit, sub_unit},
 };
 
@@ -17,6 +18,9 @@ pub struct LayoutBox {
     style: Arc<Style>,
     children: Vec<Box<dyn Layout>>,
     content_size: Option<Size>,
+    indents: Vec<Indent>,
+    // tops of lines relative to own top after lay out, if lines are horizontal
+    line_tops: Vec<Unit>,
     avoid_break: bool,
 }
 
@@ -30,6 +34,8 @@ impl LayoutBox {
             style: StyleBuilder::new().build(),
             children: vec![],
             content_size: None,
+            indents: vec![],
+            line_tops: vec![],
             avoid_break: false,
         }
     }
@@ -44,6 +50,7 @@ impl LayoutBox {
         self
     }
 
+    // Avoids breaks inside as the break inside style does, regardless of styles set later.
     pub fn avoid_break(mut self) -> Self {
         self.avoid_break = true;
         self
@@ -70,7 +77,7 @@ impl LayoutBox {
     }
 
     pub fn axis_depth(mut self, depth: impl Into<Unit>) -> Self {
-        if !matches!(self.style.align_items(), AlignItems::Baseline) {
+        if !self.style.align_items().is_baseline() {
             tracing::warn!("Depth set for a box having items not aligned on a baseline");
         }
         self.size.set_depth(Some(depth));
@@ -112,6 +119,72 @@ impl LayoutBox {
         self
     }
 
+    // Content not fitting into the box is clipped to it, or scaled down to fit inside the insets.
+    fn render_children(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        let mut inner_offset = self.offset.clone();
+        let mut inner_size = self.size.clone();
+        self.style_ref()
+            .insets()
+            .narrow(Some(&mut inner_offset), Some(&mut inner_size));
+
+        let scale = match (self.style_ref().overflow(), &self.content_size) {
+            (Overflow::ScaleDown, Some(content_size)) => {
+                let ratio = |inner: Unit, content: Unit| {
+                    if content > inner && !content.is_zero() {
+                        inner.0 as f64 / content.0 as f64
+                    } else {
+                        1.0
+                    }
+                };
+                ratio(inner_size.base_width(), content_size.base_width())
+                    .min(ratio(inner_size.base_height(), content_size.base_height()))
+            }
+            _ => 1.0,
+        };
+
+        let clip = !matches!(self.style_ref().overflow(), Overflow::Visible);
+        if clip {
+            ctx.push_clip(&self.offset, &self.size);
+        }
+        if scale < 1.0 {
+            let to_origin =
+                Affine::translation(Unit::zero() - inner_offset.x, Unit::zero() - inner_offset.y);
+            let back = Affine::translation(inner_offset.x, inner_offset.y);
+            ctx.push_transform(&to_origin.then(&Affine::scaling(scale, scale)).then(&back));
+        }
+
+        for (index, child) in self.children.iter().enumerate() {
+            // a child kept with the next one reserves room for both
+            let next = self.children[index + 1..]
+                .iter()
+                .find(|next| is_in_flow(next.as_ref()))
+                .filter(|next| {
+                    matches!(self.axis, Axis::Vertical)
+                        && is_in_flow(child.as_ref())
+                        && keeps_together(child.style_ref(), next.style_ref())
+                });
+            if let Some(next) = next {
+                let height = next.offset().y + next.size().base_height() - child.offset().y;
+                ctx.check_page_break(child.offset().y, height, true);
+            }
+
+            child.render(ctx)?;
+
+            if next.is_some() {
+                ctx.release_page_break_reservation();
+            }
+        }
+
+        if scale < 1.0 {
+            ctx.pop_transform();
+        }
+        if clip {
+            ctx.pop_clip();
+        }
+
+        Ok(())
+    }
+
     pub fn take_over_position(&mut self, offset: Offset, size: Size) {
         self.offset = offset;
         self.size = size;
@@ -165,6 +238,57 @@ impl LayoutBox {
     pub fn into_inner(self) -> impl Iterator<Item = Box<dyn Layout>> {
         self.children.into_iter()
     }
+
+    fn avoids_break(&self) -> bool {
+        self.avoid_break || matches!(self.style_ref().break_inside(), BreakInside::Avoid)
+    }
+
+    // Children are summed along the axis and the biggest one counts across it.
+    // If lines may wrap, min-content puts every child into its own line.
+    fn intrinsic_size(&mut self, ctx: &mut dyn MeasureContext, min: bool) -> Result<Size, Error> {
+        let axis = self.axis;
+        let cross = axis.cross();
+        let style = self.style.clone();
+        let wrap = min && style.wrap().unwrap_or(matches!(axis, Axis::Horizontal));
+        let axis_gap = axis.select(style.horizontal_gap_size(), style.vertical_gap_size());
+        let cross_gap = cross.select(style.horizontal_gap_size(), style.vertical_gap_size());
+
+        let mut count = 0;
+        let (mut axis_sum, mut axis_max) = (Unit::zero(), Unit::zero());
+        let (mut cross_sum, mut cross_max) = (Unit::zero(), Unit::zero());
+        for child in self
+            .children
+            .iter_mut()
+            .filter(|child| child.size_after_wrap_ref().is_some())
+        {
+            let mut size = if min {
+                child.min_content_size(ctx)?
+            } else {
+                child.max_content_size(ctx)?
+            };
+            child.style_ref().margin().widen(None, Some(&mut size));
+
+            axis_sum += axis.base_size(&size);
+            axis_max = axis_max.max(axis.base_size(&size));
+            cross_sum += cross.base_size(&size);
+            cross_max = cross_max.max(cross.base_size(&size));
+            count += 1;
+        }
+
+        let gaps = count.max(1) - 1;
+        let (axis_size, cross_size) = if wrap {
+            (axis_max, cross_sum + cross_gap * gaps)
+        } else {
+            (axis_sum + axis_gap * gaps, cross_max)
+        };
+
+        let mut size = Size::zero();
+        axis.dim_mut(&mut size).set_base(axis_size);
+        cross.dim_mut(&mut size).set_base(cross_size);
+        style.insets().widen(None, Some(&mut size));
+
+        Ok(self.size.definite_or(&size))
+    }
 }
 
 impl Position for LayoutBox {
@@ -213,20 +337,32 @@ impl Styled for LayoutBox {
 
 impl Layout for LayoutBox {
     fn measure(&mut self, ctx: &mut dyn MeasureContext, mut room: Size) -> Result<(), Error> {
+        // calc expressions refer to parent size and font size
+        let font_size = self.style.font_size(ctx);
+        if let Some(style) = self.style.resolve_padding(room.base_width(), font_size) {
+            self.style = style;
+        }
+
         let axis = self.axis;
         let axis_room = axis.base_size(&room);
-        axis.dim_mut(self.size_mut()).resolve_parented(axis_room);
+        axis.dim_mut(self.size_mut())
+            .resolve_parented(axis_room, font_size);
 
         let cross = axis.cross();
         let cross_room = cross.base_size(&room);
-        cross.dim_mut(self.size_mut()).resolve_parented(cross_room);
+        cross
+            .dim_mut(self.size_mut())
+            .resolve_parented(cross_room, font_size);
 
-        let respect_baseline = matches!(self.style_ref().align_items(), AlignItems::Baseline);
+        resolve_intrinsic(self, ctx)?;
+
+        self.size.apply_aspect_ratio(&self.style, None);
+
+        let align_items = self.style_ref().align_items();
+        let respect_baseline = align_items.is_baseline();
         let mut self_size = self.size.clone();
 
-        self.style_ref()
-            .padding()
-            .narrow(None, Some(&mut self_size));
+        self.style_ref().insets().narrow(None, Some(&mut self_size));
 
         let mut self_size = if self.children.is_empty() {
             self_size
@@ -237,7 +373,7 @@ impl Layout for LayoutBox {
                 child.measure(ctx, room.clone())?;
             }
 
-            self.style_ref().padding().narrow(None, Some(&mut room));
+            self.style_ref().insets().narrow(None, Some(&mut room));
             let axis_room = axis.base_size(&room);
             let axis_room = axis.dim(&self_size).size_available(axis_room);
             let wrap = self
@@ -254,14 +390,18 @@ impl Layout for LayoutBox {
                 self.style_ref().vertical_gap_size(),
             );
 
-            let lines = lay_out_native(
+            let collapse_margins = self.style_ref().collapse_margins().unwrap_or_default();
+            let margins = effective_margins(self.axis, &self.children, collapse_margins);
+            let lines = lay_out_native_with_margins(
                 self.axis,
                 &mut self.children,
+                margins,
                 axis_room,
                 axis_gap,
                 cross_gap,
                 wrap,
-                respect_baseline,
+                align_items,
+                &[],
             );
 
             let mut children_size =
@@ -292,15 +432,17 @@ impl Layout for LayoutBox {
             children_size
         };
 
-        self.style_ref().padding().widen(None, Some(&mut self_size));
+        self.style_ref().insets().widen(None, Some(&mut self_size));
 
         axis.dim_mut(self.size_mut())
             .resolve_content(axis.base_size(&self_size));
         cross
             .dim_mut(self.size_mut())
             .resolve_content(cross.base_size(&self_size));
+        self.size.apply_aspect_ratio(&self.style, None);
         if respect_baseline && self.size().depth().is_none() {
             self.size_mut().set_depth(self_size.depth());
+            self.size_mut().set_last_depth(self_size.last_depth());
         }
 
         Ok(())
@@ -312,13 +454,15 @@ impl Layout for LayoutBox {
         mut offset: Offset,
         mut room: Size,
     ) -> Result<(), Error> {
+        let mut ratio_size = self.size.clone();
+        ratio_size.apply_aspect_ratio(self.style_ref(), Some(&room));
+        let mut size = ratio_size.clone();
+
         // resolve padding
         self.style_ref()
-            .padding()
+            .insets()
             .narrow(Some(&mut offset), Some(&mut room));
-
-        let mut size = self.size.clone();
-        self.style_ref().padding().narrow(None, Some(&mut size));
+        self.style_ref().insets().narrow(None, Some(&mut size));
 
         // axes preparation
         let axis = self.axis;
@@ -348,26 +492,51 @@ impl Layout for LayoutBox {
         );
 
         // Resolve relative positioning of request and self ascents, when aligning to baseline.
-        let self_ascent = self.size_after_wrap_ref().and_then(|size| size.ascent());
+        let last_baseline = matches!(align_items, AlignItems::LastBaseline);
+        let self_ascent = self
+            .size_after_wrap_ref()
+            .and_then(|size| match last_baseline {
+                true => size.last_ascent(),
+                false => size.ascent(),
+            });
         if matches!(
             (align_items, room.depth(), self_ascent),
-            (AlignItems::Baseline, Some(_), Some(_))
+            (
+                AlignItems::Baseline | AlignItems::LastBaseline,
+                Some(_),
+                Some(_)
+            )
         ) {
             let self_to_parent_ascent = sub_unit(room.ascent(), self_ascent);
             offset.y_advance(self_to_parent_ascent.unwrap_or_default());
         }
 
-        // wrap children using native size
-        let lines = lay_out_native(
+        // wrap children using native size including their margins, lines flow around floats of parent
+        let collapse_margins = self.style_ref().collapse_margins().unwrap_or_default();
+        let margins = effective_margins(self.axis, &self.children, collapse_margins);
+        let insets = self.style_ref().insets();
+        let indents = self
+            .indents
+            .iter()
+            .map(|indent| indent.narrowed(&insets))
+            .collect::<Vec<_>>();
+        let lines = lay_out_native_with_margins(
             self.axis,
             &mut self.children,
+            margins,
             axis_size,
             axis_gap,
             cross_gap,
             wrap,
-            matches!(align_items, AlignItems::Baseline),
+            align_items,
+            &indents,
         );
 
+        // floats placed so far, as indents relative to the content offset
+        let mut floats: Vec<Indent> = vec![];
+        let mut float_sides: Vec<Float> = vec![];
+        let mut line_tops = vec![];
+
         // prepare loop over lines
         let mut position = offset.clone();
         let mut content_size = Size::zero();
@@ -390,16 +559,48 @@ impl Layout for LayoutBox {
                         offset.y_advance(first_line_to_self_ascent.unwrap_or_default());
                     }
 
-                    first_ascent = self_ascent.or_else(|| native_line_size.ascent());
+                    // the first line of items aligned on last baselines is not shifted
+                    first_ascent = match last_baseline {
+                        true => native_line_size.ascent(),
+                        false => self_ascent.or_else(|| native_line_size.ascent()),
+                    };
                 }
             } else {
                 position = cross.advance_dim(&position, cross_gap);
                 content_size = cross.extend_dim(&content_size, cross_gap);
             }
 
-            let room_to_distribute = axis_size - axis.base_size(&native_line_size);
-            let sum_grow = axis.dim(&native_line_size).grow;
-            let sum_shrink = axis.dim(&native_line_size).shrink;
+            // Resolve axis stretches of the whole line at once, so that the space not taken
+            // by children clamped to their min/max is redistributed among their siblings.
+            let (indent_start, indent_end) = line.indent();
+            let mut room_to_distribute =
+                axis_size - indent_start - indent_end - axis.base_size(&native_line_size);
+            let line_dims = line
+                .children()
+                .filter(|(child, _)| child.size_after_wrap_ref().is_some())
+                .map(|(child, margin)| {
+                    if matches!(axis, Axis::Vertical) && child.style_ref().float().is_some() {
+                        return FlexDim::fixed(0);
+                    }
+                    let margin = axis.select(margin.width(), margin.height());
+                    let mut dim = axis.dim(child.size_after_wrap_ref().unwrap()).clone();
+                    // basis replaces the measured size as flex base along the main axis
+                    if let Some(basis) = child.style_ref().basis() {
+                        let measured = dim.base_size();
+                        dim.set_base(basis.size(measured, axis_size));
+                        room_to_distribute += measured - dim.base_size();
+                    }
+                    let mut dim = dim + margin;
+                    if let Some(min) = dim.min.as_mut() {
+                        *min += margin;
+                    }
+                    if let Some(max) = dim.max.as_mut() {
+                        *max += margin;
+                    }
+                    dim
+                })
+                .collect::<Vec<_>>();
+            let child_axis_sizes = distribute(&line_dims, room_to_distribute);
 
             // If lines are more, or if cross axis has no dimension, use native size.
             // Otherwise (single line with cross axis known size) cross axis may stretch.
@@ -409,34 +610,84 @@ impl Layout for LayoutBox {
                 cross_size
             };
 
+            if matches!(axis, Axis::Horizontal) {
+                line_tops.push(position.y);
+            }
+
             // prepare loop over children in line
-            axis.set_offset(&mut position, axis.offset(&offset)); // reset axis offset for new line
+            axis.set_offset(&mut position, axis.offset(&offset) + indent_start); // reset axis offset for new line
             let mut line_size = Size::zero();
             let mut first_child = Some(());
 
             // childrens wipped after wrapping are already removed from lay_out_native, so the filter here is double check
-            for child in line
-                .content_mut()
-                .iter_mut()
-                .filter(|child| child.size_after_wrap_ref().is_some())
+            for ((child, margin), child_axis_size) in line
+                .children_mut()
+                .filter(|(child, _)| child.size_after_wrap_ref().is_some())
+                .zip(child_axis_sizes)
             {
+                // child occupies its margin box
+                let mut child_size = child.size_after_wrap_ref().unwrap().clone();
+                margin.widen(None, Some(&mut child_size));
+                let child_first_ascent = child_size.ascent();
+                if last_baseline {
+                    child_size = child_size.last_baseline();
+                }
+                let child_size = &child_size;
+
+                // floats are out of flow, placed aside at the current position
+                if let (Axis::Vertical, Some(float)) = (axis, child.style_ref().float()) {
+                    let top = position.y - offset.y;
+                    let width = child_size.base_width();
+                    let height = child_size.base_height();
+                    let (start, end) = Indent::at(&floats, top);
+                    let x = match float {
+                        Float::Left => start,
+                        Float::Right => cross_size - end - width,
+                    };
+
+                    let mut child_offset = Offset::new(offset.x + x, position.y);
+                    let mut float_size = Size::fixed(width, height);
+                    margin.narrow(Some(&mut child_offset), Some(&mut float_size));
+                    child.lay_out(ctx, child_offset, float_size)?;
+
+                    let (start, end) = match float {
+                        Float::Left => (x + width, Unit::zero()),
+                        Float::Right => (Unit::zero(), cross_size - x),
+                    };
+                    floats.push(Indent {
+                        top,
+                        bottom: top + height,
+                        start,
+                        end,
+                    });
+                    float_sides.push(float);
+                    continue;
+                }
+
                 let first = first_child.take();
                 if first.is_some() {
                     if matches!(axis, Axis::Vertical) {
-                        first_ascent = first_ascent
-                            .max(child.size_after_wrap_ref().and_then(|size| size.ascent()));
+                        first_ascent = first_ascent.max(child_first_ascent);
                     }
                 } else {
                     position = axis.advance_dim(&position, axis_gap);
                     line_size = axis.extend_dim(&line_size, axis_gap);
                 }
 
-                let child_size = child.size_after_wrap_ref().unwrap();
-
-                // Resolve axis streches.
-                let child_axis_size =
-                    axis.dim(child_size)
-                        .size_distributed(room_to_distribute, sum_grow, sum_shrink);
+                // clearing child moves below floats on its side
+                if let (Axis::Vertical, Some(clear)) = (axis, child.style_ref().clear()) {
+                    let cleared = floats
+                        .iter()
+                        .zip(&float_sides)
+                        .filter(|(_, side)| clear.clears(**side))
+                        .map(|(float, _)| offset.y + float.bottom)
+                        .max();
+                    if let Some(bottom) = cleared.filter(|bottom| *bottom > position.y) {
+                        let clearance = bottom - position.y;
+                        position = axis.advance_dim(&position, clearance);
+                        line_size = axis.extend_dim(&line_size, clearance);
+                    }
+                }
 
                 // Resolve cross stretches. only if both me and child has auto dimension, they stretch.
                 // The behavior is the same as in FlexBox.
@@ -472,7 +723,7 @@ impl Layout for LayoutBox {
                     (AlignItems::Start, _) => Unit::zero(),
                     (AlignItems::Center, _) => (line_cross_room - child_cross_size) * 0.5,
                     (AlignItems::End, _) => line_cross_room - child_cross_size,
-                    (AlignItems::Baseline, Axis::Horizontal) => {
+                    (AlignItems::Baseline | AlignItems::LastBaseline, Axis::Horizontal) => {
                         match (native_line_size.ascent(), child_ascent) {
                             (Some(line_ascent), Some(child_ascent)) => line_ascent - child_ascent,
                             // the following creates artificial baseline of child box in its lower edge, if the child box has no baseline
@@ -480,7 +731,9 @@ impl Layout for LayoutBox {
                             _ => Unit::zero(),
                         }
                     }
-                    (AlignItems::Baseline, Axis::Vertical) => Unit::zero(),
+                    (AlignItems::Baseline | AlignItems::LastBaseline, Axis::Vertical) => {
+                        Unit::zero()
+                    }
                 };
 
                 let cross_offsetted_position = cross.advance_dim(&position, child_cross_offset);
@@ -490,40 +743,79 @@ impl Layout for LayoutBox {
                     Axis::Horizontal => (child_axis_size, child_cross_size),
                     Axis::Vertical => (child_cross_size, child_axis_size),
                 };
-                let child_depth = child.size_after_wrap_ref().and_then(|size| size.depth());
-                let child_size = match child_depth {
+                let child_depth = child_size.depth();
+                let mut child_size = match child_depth {
                     Some(depth) => Size::fixed_depth(width, height, depth),
                     None => Size::fixed(width, height),
                 };
 
-                // recurse into
-                child.lay_out(ctx, cross_offsetted_position, child_size)?;
+                // recurse into the box inside margins
+                let mut child_offset = cross_offsetted_position;
+                margin.narrow(Some(&mut child_offset), Some(&mut child_size));
+
+                // lines of the child flow around floats overlapping it, otherwise it moves aside
+                let mut flowing = false;
+                if matches!(axis, Axis::Vertical) {
+                    let top = child_offset.y - offset.y;
+                    let left = child_offset.x - offset.x;
+                    let right = cross_size - left - child_size.base_width();
+                    let indents = floats
+                        .iter()
+                        .filter(|float| float.bottom > top)
+                        .map(|float| Indent {
+                            top: float.top - top,
+                            bottom: float.bottom - top,
+                            start: (float.start - left).max(Unit::zero()),
+                            end: (float.end - right).max(Unit::zero()),
+                        })
+                        .collect::<Vec<_>>();
+                    let (start, _) = Indent::at(&indents, Unit::zero());
+                    flowing = !indents.is_empty();
+                    if !child.flow_around(indents) {
+                        child_offset.x_advance(start);
+                        flowing = false;
+                    }
+                }
+                child.lay_out(ctx, child_offset, child_size)?;
 
                 // move forward in main axis, gap is added at the loop begin
+                // lines flowing around floats may be more than measured
+                let mut child_axis_size = child_axis_size;
+                if let Some(mut laid_out) = child.size_after_lay_out().filter(|_| flowing) {
+                    margin.widen(None, Some(&mut laid_out));
+                    child_axis_size = child_axis_size.max(axis.base_size(&laid_out));
+                }
                 position = axis.advance_dim(&position, child_axis_size);
 
                 // line_child_size incorporates bounding box of child offsetted in both axes.
                 // line_child_size can be bigger than child_size.
-                if let Some(line_child_size) = child.size_after_lay_out() {
+                if let Some(mut line_child_size) = child.size_after_lay_out() {
+                    margin.widen(None, Some(&mut line_child_size));
+                    if last_baseline {
+                        line_child_size = line_child_size.last_baseline();
+                    }
                     let line_child_size = axis.extend_dim(&line_child_size, child_axis_offset);
                     let line_child_size = cross.extend_dim(&line_child_size, child_cross_offset);
 
-                    line_size = axis.extend_size(
-                        &line_size,
-                        &line_child_size,
-                        matches!(align_items, AlignItems::Baseline),
-                    );
+                    line_size =
+                        axis.extend_size(&line_size, &line_child_size, align_items.is_baseline());
                 }
             }
 
             // Move forward in cross axis (over lines), gap is added at the loop begin.
             // Multiple lines never stretch (the same behavior as FlexBox has).
             position = cross.advance_dim(&position, cross.base_size(&line_size));
-            content_size = cross.extend_size(
-                &content_size,
-                &line_size,
-                matches!(align_items, AlignItems::Baseline),
-            );
+            content_size = cross.extend_size(&content_size, &line_size, align_items.is_baseline());
+        }
+
+        // Floats extend the content as well.
+        if let Some(bottom) = floats
+            .iter()
+            .map(|float| float.bottom)
+            .max()
+            .filter(|bottom| *bottom > content_size.base_height())
+        {
+            content_size.set_base_height(bottom);
         }
 
         // Sets own axis/cross dimensions to native content size, if the dimension is auto_fixed.
@@ -535,9 +827,18 @@ impl Layout for LayoutBox {
 
         // Adopt final offset and size including padding
         self.style_ref()
-            .padding()
+            .insets()
             .widen(Some(&mut offset), Some(&mut size));
 
+        // Aspect ratio wins over content size.
+        if self.style_ref().aspect_ratio().is_some()
+            && (self.size.width.is_content() || self.size.height.is_content())
+        {
+            size.width.base = ratio_size.width.base;
+            size.height.base = ratio_size.height.base;
+        }
+
+        self.line_tops = line_tops.into_iter().map(|top| top - offset.y).collect();
         self.offset = offset;
         self.size = size;
 
@@ -547,60 +848,209 @@ impl Layout for LayoutBox {
                 .set_depth(first_ascent.map(|first_ascent| self.size.base_height() - first_ascent));
         }
 
+        // The last baseline is the one of the last line, below the top inset.
+        if align_items.is_baseline() {
+            let content_size = self.content_size.as_ref();
+            if let Some(last_ascent) = content_size.and_then(|size| size.last_ascent()) {
+                let last_ascent = insets.top_size() + last_ascent;
+                self.size
+                    .set_last_depth(Some(self.size.base_height() - last_ascent));
+            }
+        }
+
         Ok(())
     }
 
-    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
-        if self.avoid_break {
-            ctx.check_page_break(self.offset.y, self.size.height.base_size(), true);
+    fn flow_around(&mut self, indents: Vec<Indent>) -> bool {
+        if matches!(self.axis, Axis::Horizontal) {
+            self.indents = indents;
+            true
+        } else {
+            false
         }
+    }
 
-        for child in self.iter() {
-            child.render(ctx)?;
+    // Lines break between them, leaving at least orphans above and widows below the break.
+    // Stacked children break between and inside them, unless their style avoids it.
+    fn breaks(&self) -> Vec<Unit> {
+        let forced = self.forced_breaks().into_iter().map(|(at, _)| at);
+        if self.avoids_break() {
+            return forced.collect();
         }
 
-        let style = self.style_ref();
-        let top_left = self.offset();
-        let bottom_right = top_left + &self.size;
+        if matches!(self.axis, Axis::Horizontal) {
+            let orphans = self.style_ref().orphans();
+            let widows = self.style_ref().widows();
+            let lines = self.line_tops.len();
+            return self
+                .line_tops
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index >= orphans && lines - index >= widows)
+                .map(|(_, top)| *top)
+                .collect();
+        }
 
-        if let Some(stroke) = style.border_top() {
-            ctx.line(
-                &self.offset,
-                &Offset::new(bottom_right.x, top_left.y),
-                stroke,
-            );
+        let mut breaks = vec![];
+        let mut previous: Option<&Style> = None;
+        let mut page_break = None;
+        for child in self.children.iter() {
+            if let Some(between) = child.page_break().map(|page_break| page_break.between()) {
+                page_break = Some(between);
+            }
+            if !is_in_flow(child.as_ref()) {
+                continue;
+            }
+            let page_break = page_break.take();
+            let top = child.offset().y - self.offset.y;
+            if let Some(previous) = previous
+                && (page_break.is_some() || !keeps_together(previous, child.style_ref()))
+            {
+                breaks.push(top);
+            }
+            breaks.extend(child.breaks().into_iter().map(|at| top + at));
+            previous = Some(child.style_ref());
         }
+        breaks
+    }
 
-        if let Some(stroke) = style.border_right() {
-            ctx.line(
-                &Offset::new(bottom_right.x, top_left.y),
-                &bottom_right,
-                stroke,
-            );
+    // Breaks forced by styles of stacked children or page breaks between them, in them too.
+    fn forced_breaks(&self) -> Vec<(Unit, BreakBetween)> {
+        if matches!(self.axis, Axis::Horizontal) {
+            return vec![];
         }
 
-        if let Some(stroke) = style.border_bottom() {
-            ctx.line(
-                &bottom_right,
-                &Offset::new(top_left.x, bottom_right.y),
-                stroke,
+        let mut breaks = vec![];
+        let mut previous: Option<&Style> = None;
+        let mut page_break = None;
+        for child in self.children.iter() {
+            if let Some(between) = child.page_break().map(|page_break| page_break.between()) {
+                page_break = Some(between);
+            }
+            if !is_in_flow(child.as_ref()) {
+                continue;
+            }
+            let page_break = page_break.take();
+            let top = child.offset().y - self.offset.y;
+            if let Some(previous) = previous {
+                // the later of the breaks decides the side of the page
+                let forced = [child.style_ref().break_before(), previous.break_after()]
+                    .into_iter()
+                    .chain(page_break)
+                    .find(|between| between.is_forced());
+                if let Some(forced) = forced {
+                    breaks.push((top, forced));
+                }
+            }
+            breaks.extend(
+                child
+                    .forced_breaks()
+                    .into_iter()
+                    .map(|(at, between)| (top + at, between)),
             );
+            previous = Some(child.style_ref());
         }
+        breaks
+    }
+
+    // Lines or children starting from the break go into a new box of the same style,
+    // a stacked child broken inside leads them with its part below the break.
+    fn split_off(&mut self, at: Unit) -> Option<Box<dyn Layout>> {
+        let top = self.breaks().into_iter().filter(|top| *top <= at).max()?;
+        let threshold = self.offset.y + top;
+        let broken = match self.axis {
+            Axis::Horizontal => None,
+            Axis::Vertical => self.children.iter().position(|child| {
+                is_in_flow(child.as_ref())
+                    && child.offset().y < threshold
+                    && threshold < child.offset().y + child.size().base_height()
+            }),
+        };
 
-        if let Some(stroke) = style.border_left() {
-            ctx.line(&Offset::new(top_left.x, bottom_right.y), top_left, stroke);
+        let children = match broken {
+            Some(index) => {
+                let child_top = self.children[index].offset().y - self.offset.y;
+                let part = self.children[index].split_off(top - child_top)?;
+                let mut children = self.children.split_off(index + 1);
+                children.insert(0, part);
+                children
+            }
+            None => {
+                let mut index = self.children.iter().position(|child| {
+                    child.size_after_wrap_ref().is_some() && child.offset().y >= threshold
+                })?;
+                // markers taking no space go with the child following them
+                while index > 0 && self.children[index - 1].size_after_wrap_ref().is_none() {
+                    index -= 1;
+                }
+                self.children.split_off(index)
+            }
+        };
+
+        self.line_tops.clear();
+        self.content_size = None;
+        self.size.set_base_height(Dim::content());
+
+        let mut tail = LayoutBox::new(self.axis);
+        tail.mark = self.mark;
+        tail.size = self.size.clone();
+        tail.style = self.style.clone();
+        tail.avoid_break = self.avoid_break;
+        tail.children = children;
+        Some(Box::new(tail))
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        let avoid_break = self.avoids_break();
+        if avoid_break {
+            ctx.check_page_break(self.offset.y, self.size.height.base_size(), true);
         }
 
+        self.render_children(ctx)?;
+
+        self.style_ref()
+            .render_border(ctx, self.offset(), self.size());
+
         ctx.debug_frame(self.offset(), self.size());
 
-        if self.avoid_break {
+        if avoid_break {
             ctx.release_page_break_reservation();
         }
 
         Ok(())
     }
 
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, true)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, false)
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.children
+            .iter_mut()
+            .flat_map(|child| child.footnotes_mut())
+            .collect()
+    }
+
     fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
         Box::new(self.children.iter())
     }
 }
+
+// Stacked children taking space, i.e. neither floats nor breaks.
+fn is_in_flow(child: &dyn Layout) -> bool {
+    child.size_after_wrap_ref().is_some() && child.style_ref().float().is_none()
+}
+
+// No page break between stacked siblings, unless forced by either of them.
+fn keeps_together(previous: &Style, next: &Style) -> bool {
+    let between = (previous.break_after(), next.break_before());
+    if between.0.is_forced() || between.1.is_forced() {
+        return false;
+    }
+    previous.keep_with_next()
+        || matches!(between, (BreakBetween::Avoid, _) | (_, BreakBetween::Avoid))
+}
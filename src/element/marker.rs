@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use crate::{
+    Error, Layout, MeasureContext, Position, Style, Styled,
+    position::{Offset, Size},
+};
+
+// Text captured from the flow for running headers and footers, e.g. the current chapter or
+// the first entry of a dictionary page. Markers of a name are resolved per page.
+pub struct Marker {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    name: String,
+    text: String,
+}
+
+impl Marker {
+    pub fn new(name: impl ToString, text: impl ToString) -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::none(),
+            style: Style::new(),
+            name: name.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Position for Marker {
+    fn element(&self) -> &str {
+        "Marker"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &Size::NONE
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    fn size_after_wrap_ref(&self) -> Option<&Size> {
+        None
+    }
+
+    fn size_after_lay_out(&self) -> Option<Size> {
+        None
+    }
+}
+
+impl Styled for Marker {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.style = style;
+    }
+}
+
+impl Layout for Marker {
+    fn lay_out(
+        &mut self,
+        _: &mut dyn MeasureContext,
+        position: Offset,
+        _: Size,
+    ) -> Result<(), Error> {
+        self.offset = position;
+        Ok(())
+    }
+
+    fn marker(&self) -> Option<&Marker> {
+        Some(self)
+    }
+}
@@ -1,15 +1,19 @@
 use std::sync::Arc;
 
 use crate::{
-    Error, Layout, MeasureContext, Position, RenderContext, Style, Styled,
+    BreakBetween, Error, Layout, MeasureContext, NewPageOptions, Position, RenderContext, Style,
+    Styled,
     position::{Offset, Size},
 };
 
+// Break to a new page, set up by the options if any, between the stacked siblings around it.
 pub struct PageBreak {
     mark: Option<&'static str>,
     offset: Offset,
     size: Size,
     style: Arc<Style>,
+    between: BreakBetween,
+    options: Option<NewPageOptions>,
 }
 
 impl PageBreak {
@@ -19,6 +23,8 @@ impl PageBreak {
             offset: Offset::zero(),
             size: Size::none(),
             style: Style::new(),
+            between: BreakBetween::Page,
+            options: None,
         }
     }
 
@@ -26,6 +32,31 @@ impl PageBreak {
         self.mark = Some(mark);
         self
     }
+
+    pub fn options(mut self, options: impl Into<Option<NewPageOptions>>) -> Self {
+        self.options = options.into();
+        self
+    }
+
+    // Breaks to the next odd (right) page, leaving an even page blank if needed.
+    pub fn to_odd(mut self) -> Self {
+        self.between = BreakBetween::Right;
+        self
+    }
+
+    // Breaks to the next even (left) page, leaving an odd page blank if needed.
+    pub fn to_even(mut self) -> Self {
+        self.between = BreakBetween::Left;
+        self
+    }
+
+    pub fn between(&self) -> BreakBetween {
+        self.between
+    }
+
+    pub fn options_ref(&self) -> Option<&NewPageOptions> {
+        self.options.as_ref()
+    }
 }
 
 impl Position for PageBreak {
@@ -88,7 +119,16 @@ impl Layout for PageBreak {
     }
 
     fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
-        ctx.new_page(None);
+        ctx.new_page(self.options.clone());
+        if let Some(number) = ctx.page_number()
+            && !self.between.fits_page(number)
+        {
+            ctx.new_page(self.options.clone());
+        }
         Ok(())
     }
+
+    fn page_break(&self) -> Option<&PageBreak> {
+        Some(self)
+    }
 }
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::{
+    Error, Layout, MeasureContext, Position, Style, Styled,
+    position::{Offset, Size},
+};
+
+// Start of a section in the flow, its title is the section title field of pages from there on.
+pub struct Section {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    title: String,
+}
+
+impl Section {
+    pub fn new(title: impl ToString) -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::none(),
+            style: Style::new(),
+            title: title.to_string(),
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+}
+
+impl Position for Section {
+    fn element(&self) -> &str {
+        "Section"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &Size::NONE
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    fn size_after_wrap_ref(&self) -> Option<&Size> {
+        None
+    }
+
+    fn size_after_lay_out(&self) -> Option<Size> {
+        None
+    }
+}
+
+impl Styled for Section {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.style = style;
+    }
+}
+
+impl Layout for Section {
+    fn lay_out(
+        &mut self,
+        _: &mut dyn MeasureContext,
+        position: Offset,
+        _: Size,
+    ) -> Result<(), Error> {
+        self.offset = position;
+        Ok(())
+    }
+
+    fn section_title(&self) -> Option<&str> {
+        Some(&self.title)
+    }
+}
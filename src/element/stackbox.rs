@@ -0,0 +1,348 @@
+use std::sync::Arc;
+
+use crate::{
+    Axis, Error, Footnote, Layout, MeasureContext, Position, RenderContext, Style, StyleBuilder,
+    Styled,
+    dimension::{Dim, FlexDim, MaybeDim},
+    layout::resolve_intrinsic,
+    position::{Offset, Size},
+    unit::Unit,
+};
+
+// Alignment of a StackBox child in one axis, stretching applies to children of content size.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+impl StackAlign {
+    // Offset and size of the child in the room.
+    fn place(&self, room: Unit, size: Unit, stretches: bool) -> (Unit, Unit) {
+        match self {
+            Self::Start => (Unit::zero(), size),
+            Self::Center => ((room - size) * 0.5, size),
+            Self::End => (room - size, size),
+            Self::Stretch if stretches => (Unit::zero(), room),
+            Self::Stretch => (Unit::zero(), size),
+        }
+    }
+}
+
+// Children occupy the same area one over another, painted in order of children.
+pub struct StackBox {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    children: Vec<Box<dyn Layout>>,
+    aligns: Vec<(StackAlign, StackAlign)>,
+}
+
+impl StackBox {
+    pub fn new() -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::content(),
+            style: StyleBuilder::new().build(),
+            children: vec![],
+            aligns: vec![],
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn width(mut self, size: impl Into<Dim>) -> Self {
+        self.size.width.set_base(size);
+        self
+    }
+
+    pub fn min_width(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.width.set_min(size);
+        self
+    }
+
+    pub fn max_width(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.width.set_max(size);
+        self
+    }
+
+    pub fn height(mut self, size: impl Into<Dim>) -> Self {
+        self.size.height.set_base(size);
+        self
+    }
+
+    pub fn min_height(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.height.set_min(size);
+        self
+    }
+
+    pub fn max_height(mut self, size: impl Into<MaybeDim>) -> Self {
+        self.size.height.set_max(size);
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(&self.style));
+        self
+    }
+
+    pub fn child(self, child: impl Layout + 'static) -> Self {
+        self.child_dyn(Box::new(child))
+    }
+
+    pub fn child_dyn(self, child: Box<dyn Layout>) -> Self {
+        self.child_aligned_dyn(StackAlign::Start, StackAlign::Start, child)
+    }
+
+    pub fn child_aligned(
+        self,
+        horizontal: StackAlign,
+        vertical: StackAlign,
+        child: impl Layout + 'static,
+    ) -> Self {
+        self.child_aligned_dyn(horizontal, vertical, Box::new(child))
+    }
+
+    pub fn child_aligned_dyn(
+        mut self,
+        horizontal: StackAlign,
+        vertical: StackAlign,
+        mut child: Box<dyn Layout>,
+    ) -> Self {
+        let style = child.style_ref().inherit(self.style_ref());
+        child.size_mut().apply_style(Axis::Horizontal, &style);
+        child.set_style(style);
+        self.children.push(child);
+        self.aligns.push((horizontal, vertical));
+        self
+    }
+
+    // The box is as big as its biggest child including margins.
+    fn intrinsic_size(&mut self, ctx: &mut dyn MeasureContext, min: bool) -> Result<Size, Error> {
+        let mut size = Size::zero();
+        for child in self.children.iter_mut() {
+            let mut child_size = if min {
+                child.min_content_size(ctx)?
+            } else {
+                child.max_content_size(ctx)?
+            };
+            child
+                .style_ref()
+                .margin()
+                .widen(None, Some(&mut child_size));
+            size = Size::fixed(
+                size.base_width().max(child_size.base_width()),
+                size.base_height().max(child_size.base_height()),
+            );
+        }
+
+        self.style_ref().insets().widen(None, Some(&mut size));
+
+        Ok(self.size.definite_or(&size))
+    }
+}
+
+impl Default for StackBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Position for StackBox {
+    fn element(&self) -> &str {
+        "StackBox"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &self.size
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+}
+
+impl Styled for StackBox {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.size.apply_style(Axis::Horizontal, &style);
+        self.style = style;
+    }
+}
+
+impl Layout for StackBox {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, mut room: Size) -> Result<(), Error> {
+        // calc expressions refer to parent size and font size
+        let font_size = self.style.font_size(ctx);
+        if let Some(style) = self.style.resolve_padding(room.base_width(), font_size) {
+            self.style = style;
+        }
+
+        self.size
+            .width
+            .resolve_parented(room.base_width(), font_size);
+        self.size
+            .height
+            .resolve_parented(room.base_height(), font_size);
+
+        resolve_intrinsic(self, ctx)?;
+
+        self.size.apply_aspect_ratio(&self.style, None);
+
+        // every child is measured against the same room
+        self.style_ref().insets().narrow(None, Some(&mut room));
+        let mut content_size = Size::zero();
+        for child in self.children.iter_mut() {
+            child.measure(ctx, room.clone())?;
+            if let Some(mut child_size) = child.size_after_wrap_ref().cloned() {
+                child
+                    .style_ref()
+                    .margin()
+                    .widen(None, Some(&mut child_size));
+                content_size = Size::fixed(
+                    content_size.base_width().max(child_size.base_width()),
+                    content_size.base_height().max(child_size.base_height()),
+                );
+            }
+        }
+
+        self.style_ref()
+            .insets()
+            .widen(None, Some(&mut content_size));
+
+        self.size.width.resolve_content(content_size.base_width());
+        self.size.height.resolve_content(content_size.base_height());
+        self.size.apply_aspect_ratio(&self.style, None);
+
+        Ok(())
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        mut offset: Offset,
+        mut room: Size,
+    ) -> Result<(), Error> {
+        self.size.apply_aspect_ratio(&self.style, Some(&room));
+
+        // resolve padding
+        self.style_ref()
+            .insets()
+            .narrow(Some(&mut offset), Some(&mut room));
+
+        let mut size = self.size.clone();
+        self.style_ref().insets().narrow(None, Some(&mut size));
+        // the area is the biggest child, unless own size is given or stretches
+        let area = |dim: &FlexDim, room: Unit| {
+            if dim.is_content_fixed() {
+                dim.base_size()
+            } else {
+                dim.size_available(room)
+            }
+        };
+        let width = area(&size.width, room.base_width());
+        let height = area(&size.height, room.base_height());
+
+        for (child, (horizontal, vertical)) in self.children.iter_mut().zip(&self.aligns) {
+            let Some(mut child_size) = child.size_after_wrap_ref().cloned() else {
+                continue;
+            };
+
+            // child occupies its margin box
+            let margin = child.style_ref().margin().clone();
+            margin.widen(None, Some(&mut child_size));
+
+            let stretch_width = child_size.width.is_content_or_dyn();
+            let stretch_height = child_size.height.is_content_or_dyn();
+            let (x, child_width) = horizontal.place(width, child_size.base_width(), stretch_width);
+            let (y, child_height) =
+                vertical.place(height, child_size.base_height(), stretch_height);
+
+            // stretched children get their own size fixed to the room
+            let mut child_offset = Offset::new(offset.x + x, offset.y + y);
+            let mut child_size = Size::fixed(child_width, child_height);
+            margin.narrow(Some(&mut child_offset), Some(&mut child_size));
+            if matches!(horizontal, StackAlign::Stretch) && stretch_width {
+                child.size_mut().set_base_width(child_size.base_width());
+            }
+            if matches!(vertical, StackAlign::Stretch) && stretch_height {
+                child.size_mut().set_base_height(child_size.base_height());
+            }
+            child.lay_out(ctx, child_offset, child_size)?;
+        }
+
+        let content_size = Size::fixed(width, height);
+        Axis::Horizontal.resolve_content_size(&mut size, &content_size, room.base_width());
+        Axis::Vertical.resolve_content_size(&mut size, &content_size, room.base_height());
+
+        // Adopt final offset and size including padding
+        self.style_ref()
+            .insets()
+            .widen(Some(&mut offset), Some(&mut size));
+
+        self.offset = offset;
+        self.size = size;
+
+        Ok(())
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        for child in self.iter() {
+            child.render(ctx)?;
+        }
+
+        self.style_ref()
+            .render_border(ctx, self.offset(), self.size());
+
+        ctx.debug_frame(self.offset(), self.size());
+
+        Ok(())
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, true)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.intrinsic_size(ctx, false)
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.children
+            .iter_mut()
+            .flat_map(|child| child.footnotes_mut())
+            .collect()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
+        Box::new(self.children.iter())
+    }
+}
@@ -3,9 +3,9 @@ use std::sync::Arc;
 use smol_str::{SmolStr, ToSmolStr};
 
 use crate::{
-    Features, Rgba, Styled,
-    dimension::{Dim, MaybeDim},
-    position::Quad,
+    Features, MeasureContext, RenderContext, Rgba, Styled,
+    dimension::{Calc, Dim, MaybeDim},
+    position::{CalcQuad, Offset, Quad, Size},
     unit::{Fill, FillPerMille, Pt, Unit},
 };
 
@@ -14,10 +14,114 @@ pub enum AlignItems {
     #[default]
     Start,
     Baseline,
+    LastBaseline,
     Center,
     End,
 }
 
+impl AlignItems {
+    // Items align on their first or last baseline.
+    pub fn is_baseline(&self) -> bool {
+        matches!(self, Self::Baseline | Self::LastBaseline)
+    }
+}
+
+// Flex base size in the main axis, auto takes the width or height.
+#[derive(Default, Debug, Clone, Copy)]
+pub enum Basis {
+    #[default]
+    Auto,
+    Content,
+    Fixed(Unit),
+    Parent(FillPerMille),
+}
+
+impl Basis {
+    pub fn dim(&self, preferred: Dim) -> Dim {
+        match self {
+            Self::Auto => preferred,
+            Self::Content => Dim::content(),
+            Self::Fixed(size) => (*size).into(),
+            Self::Parent(fill) => (*fill).into(),
+        }
+    }
+
+    // Flex base along the main axis of the parent, auto and content keep the measured size.
+    pub fn size(&self, measured: Unit, parent: Unit) -> Unit {
+        match self {
+            Self::Auto | Self::Content => measured,
+            Self::Fixed(size) => *size,
+            Self::Parent(fill) => parent * (*fill, FillPerMille::mille()),
+        }
+    }
+}
+
+// Content not fitting into a box is painted outside, clipped, or proportionally scaled down to fit.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    #[default]
+    Visible,
+    Clip,
+    ScaleDown,
+}
+
+// Side of a vertical box, which a child is taken out of flow to, following lines flow around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Float {
+    Left,
+    Right,
+}
+
+// Floats on the side a child moves below of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clear {
+    Left,
+    Right,
+    Both,
+}
+
+impl Clear {
+    pub fn clears(&self, float: Float) -> bool {
+        matches!(
+            (self, float),
+            (Self::Both, _) | (Self::Left, Float::Left) | (Self::Right, Float::Right)
+        )
+    }
+}
+
+// Page break before or after a box, left and right ones continue on a page of that side.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakBetween {
+    #[default]
+    Auto,
+    Avoid,
+    Page,
+    Left,
+    Right,
+}
+
+impl BreakBetween {
+    pub fn is_forced(&self) -> bool {
+        matches!(self, Self::Page | Self::Left | Self::Right)
+    }
+
+    // Whether the page numbered from one is on the side of the break, odd pages are right ones.
+    pub fn fits_page(&self, number: usize) -> bool {
+        match self {
+            Self::Left => number.is_multiple_of(2),
+            Self::Right => !number.is_multiple_of(2),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakInside {
+    #[default]
+    Auto,
+    Avoid,
+}
+
 #[derive(Debug, Clone)]
 pub struct Font {
     name: Option<SmolStr>,
@@ -235,6 +339,25 @@ impl Border {
     pub fn right(&self) -> Option<&Stroke> {
         self.right.as_ref()
     }
+
+    // Thickness of strokes as space taken on each side.
+    pub fn widths(&self) -> Quad {
+        let width = |stroke: Option<&Stroke>| stroke.map(|stroke| Unit::from(stroke.thickness));
+        let mut widths = Quad::empty();
+        if let Some(top) = width(self.top()) {
+            widths = widths.with_top(top);
+        }
+        if let Some(left) = width(self.left()) {
+            widths = widths.with_left(left);
+        }
+        if let Some(bottom) = width(self.bottom()) {
+            widths = widths.with_bottom(bottom);
+        }
+        if let Some(right) = width(self.right()) {
+            widths = widths.with_right(right);
+        }
+        widths
+    }
 }
 
 impl From<&Border> for Border {
@@ -243,7 +366,7 @@ impl From<&Border> for Border {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Style {
     font: Font,
     color: Option<Rgba>,
@@ -254,6 +377,8 @@ pub struct Style {
     height: Dim,
     min_height: MaybeDim,
     max_height: MaybeDim,
+    aspect_ratio: Option<(Fill, Fill)>,
+    basis: Option<Basis>,
     grow: Option<Fill>,
     shrink: Option<Fill>,
     wrap: Option<bool>,
@@ -262,6 +387,19 @@ pub struct Style {
     vertical_gap: Option<Unit>,
     border: Border,
     padding: Quad,
+    padding_calc: Option<CalcQuad>,
+    margin: Quad,
+    collapse_margins: Option<bool>,
+    border_box: Option<bool>,
+    float: Option<Float>,
+    clear: Option<Clear>,
+    overflow: Option<Overflow>,
+    widows: Option<usize>,
+    orphans: Option<usize>,
+    break_before: Option<BreakBetween>,
+    break_after: Option<BreakBetween>,
+    break_inside: Option<BreakInside>,
+    keep_with_next: Option<bool>,
 }
 
 impl Styled for Arc<Style> {
@@ -286,6 +424,8 @@ impl Style {
             height: Dim::content(),
             min_height: MaybeDim::None,
             max_height: MaybeDim::None,
+            aspect_ratio: None,
+            basis: None,
             grow: None,
             shrink: None,
             wrap: None,
@@ -294,6 +434,19 @@ impl Style {
             vertical_gap: None,
             border: Border::none(),
             padding: Quad::empty(),
+            padding_calc: None,
+            margin: Quad::empty(),
+            collapse_margins: None,
+            border_box: None,
+            float: None,
+            clear: None,
+            overflow: None,
+            widows: None,
+            orphans: None,
+            break_before: None,
+            break_after: None,
+            break_inside: None,
+            keep_with_next: None,
         }
     }
 
@@ -317,9 +470,9 @@ impl Style {
 
     pub fn inherit(&self, parent: &Style) -> Arc<Self> {
         let align_items = if matches!(self.align_items, None)
-            && matches!(parent.align_items, Some(AlignItems::Baseline))
+            && parent.align_items.is_some_and(|align| align.is_baseline())
         {
-            Some(AlignItems::Baseline)
+            parent.align_items
         } else {
             self.align_items
         };
@@ -338,6 +491,8 @@ impl Style {
             height: self.height.clone(),
             min_height: self.min_height.clone(),
             max_height: self.max_height.clone(),
+            aspect_ratio: self.aspect_ratio,
+            basis: self.basis,
             grow: self.grow,
             shrink: self.shrink,
             wrap: self.wrap,
@@ -346,6 +501,19 @@ impl Style {
             vertical_gap: self.vertical_gap,
             border: self.border.clone(),
             padding: self.padding.clone(),
+            padding_calc: self.padding_calc.clone(),
+            margin: self.margin.clone(),
+            collapse_margins: self.collapse_margins,
+            border_box: self.border_box,
+            float: self.float,
+            clear: self.clear,
+            overflow: self.overflow,
+            widows: self.widows.or(parent.widows),
+            orphans: self.orphans.or(parent.orphans),
+            break_before: self.break_before,
+            break_after: self.break_after,
+            break_inside: self.break_inside,
+            keep_with_next: self.keep_with_next,
         })
     }
 
@@ -364,6 +532,8 @@ impl Style {
             height: self.height.or(parent.height),
             min_height: self.min_height.or(parent.min_height),
             max_height: self.max_height.or(parent.max_height),
+            aspect_ratio: self.aspect_ratio.or(parent.aspect_ratio),
+            basis: self.basis.or(parent.basis),
             grow: self.grow.as_ref().or(parent.grow.as_ref()).cloned(),
             shrink: self.shrink.as_ref().or(parent.shrink.as_ref()).cloned(),
             wrap: self.wrap.as_ref().or(parent.wrap.as_ref()).cloned(),
@@ -372,6 +542,23 @@ impl Style {
             vertical_gap: self.vertical_gap.or(parent.vertical_gap),
             border: self.border.merge(&parent.border),
             padding: self.padding.merge(&parent.padding),
+            padding_calc: self
+                .padding_calc
+                .as_ref()
+                .or(parent.padding_calc.as_ref())
+                .cloned(),
+            margin: self.margin.merge(&parent.margin),
+            collapse_margins: self.collapse_margins.or(parent.collapse_margins),
+            border_box: self.border_box.or(parent.border_box),
+            float: self.float.or(parent.float),
+            clear: self.clear.or(parent.clear),
+            overflow: self.overflow.or(parent.overflow),
+            widows: self.widows.or(parent.widows),
+            orphans: self.orphans.or(parent.orphans),
+            break_before: self.break_before.or(parent.break_before),
+            break_after: self.break_after.or(parent.break_after),
+            break_inside: self.break_inside.or(parent.break_inside),
+            keep_with_next: self.keep_with_next.or(parent.keep_with_next),
         })
     }
 
@@ -379,6 +566,15 @@ impl Style {
         &self.font
     }
 
+    // Font size em parts of calc expressions refer to, the one of the context if there is none.
+    pub fn font_size(&self, ctx: &dyn MeasureContext) -> Unit {
+        self.font
+            .size()
+            .or_else(|| ctx.style().font().size())
+            .map(Unit::from)
+            .unwrap_or_default()
+    }
+
     pub fn color(&self) -> Option<&Rgba> {
         self.color.as_ref()
     }
@@ -411,6 +607,15 @@ impl Style {
         self.max_height
     }
 
+    // Ratio of width to height.
+    pub fn aspect_ratio(&self) -> Option<(Fill, Fill)> {
+        self.aspect_ratio
+    }
+
+    pub fn basis(&self) -> Option<Basis> {
+        self.basis
+    }
+
     pub fn grow(&self) -> Option<Fill> {
         self.grow
     }
@@ -423,6 +628,10 @@ impl Style {
         self.wrap
     }
 
+    pub fn border(&self) -> &Border {
+        &self.border
+    }
+
     pub fn border_top(&self) -> Option<&Stroke> {
         self.border.top()
     }
@@ -439,6 +648,144 @@ impl Style {
         self.border.right()
     }
 
+    pub fn margin(&self) -> &Quad {
+        &self.margin
+    }
+
+    pub fn margin_top_size(&self) -> Unit {
+        self.margin.top_size()
+    }
+
+    pub fn margin_left_size(&self) -> Unit {
+        self.margin.left_size()
+    }
+
+    pub fn margin_bottom_size(&self) -> Unit {
+        self.margin.bottom_size()
+    }
+
+    pub fn margin_right_size(&self) -> Unit {
+        self.margin.right_size()
+    }
+
+    pub fn collapse_margins(&self) -> Option<bool> {
+        self.collapse_margins
+    }
+
+    pub fn border_box(&self) -> Option<bool> {
+        self.border_box
+    }
+
+    pub fn float(&self) -> Option<Float> {
+        self.float
+    }
+
+    pub fn clear(&self) -> Option<Clear> {
+        self.clear
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow.unwrap_or_default()
+    }
+
+    // Minimum of lines of a paragraph left at the top of the next page.
+    pub fn widows(&self) -> usize {
+        self.widows.unwrap_or(1).max(1)
+    }
+
+    // Minimum of lines of a paragraph left at the bottom of the page.
+    pub fn orphans(&self) -> usize {
+        self.orphans.unwrap_or(1).max(1)
+    }
+
+    pub fn break_before(&self) -> BreakBetween {
+        self.break_before.unwrap_or_default()
+    }
+
+    pub fn break_after(&self) -> BreakBetween {
+        self.break_after.unwrap_or_default()
+    }
+
+    pub fn break_inside(&self) -> BreakInside {
+        self.break_inside.unwrap_or_default()
+    }
+
+    // The box stays on the page of its next sibling.
+    pub fn keep_with_next(&self) -> bool {
+        self.keep_with_next.unwrap_or_default()
+    }
+
+    // Space between the box edge and its content, border widths count only in border box mode.
+    pub fn insets(&self) -> Quad {
+        if self.border_box.unwrap_or_default() {
+            &self.padding + &self.border.widths()
+        } else {
+            self.padding.clone()
+        }
+    }
+
+    // Corners of the rectangle, which border lines are drawn along.
+    // In border box mode lines are centred inside the border area, otherwise they lie on the box edge.
+    pub fn border_frame(&self, offset: &Offset, size: &Size) -> (Offset, Offset) {
+        let mut size = size.clone();
+        if !self.border_box.unwrap_or_default() {
+            return (offset.clone(), offset + &size);
+        }
+
+        let widths = self.border.widths();
+        let half = Quad::empty()
+            .with_top(widths.top_size() * 0.5)
+            .with_left(widths.left_size() * 0.5)
+            .with_bottom(widths.bottom_size() * 0.5)
+            .with_right(widths.right_size() * 0.5);
+        let mut offset = offset.clone();
+        half.narrow(Some(&mut offset), Some(&mut size));
+        let bottom_right = &offset + &size;
+        (offset, bottom_right)
+    }
+
+    // Draws the border lines along the border frame.
+    pub(crate) fn render_border(&self, ctx: &mut dyn RenderContext, offset: &Offset, size: &Size) {
+        let (top_left, bottom_right) = self.border_frame(offset, size);
+        let top_left = &top_left;
+
+        if let Some(stroke) = self.border_top() {
+            ctx.line(top_left, &Offset::new(bottom_right.x, top_left.y), stroke);
+        }
+
+        if let Some(stroke) = self.border_right() {
+            ctx.line(
+                &Offset::new(bottom_right.x, top_left.y),
+                &bottom_right,
+                stroke,
+            );
+        }
+
+        if let Some(stroke) = self.border_bottom() {
+            ctx.line(
+                &bottom_right,
+                &Offset::new(top_left.x, bottom_right.y),
+                stroke,
+            );
+        }
+
+        if let Some(stroke) = self.border_left() {
+            ctx.line(&Offset::new(top_left.x, bottom_right.y), top_left, stroke);
+        }
+    }
+
+    // Style with calc padding resolved against parent width and font size, if there is any.
+    pub fn resolve_padding(&self, parent_width: Unit, font_size: Unit) -> Option<Arc<Self>> {
+        let padding_calc = self.padding_calc.as_ref()?;
+        let padding = padding_calc
+            .resolve(parent_width, font_size)
+            .merge(&self.padding);
+        Some(Arc::new(Self {
+            padding,
+            ..self.clone()
+        }))
+    }
+
     pub fn padding(&self) -> &Quad {
         &self.padding
     }
@@ -575,6 +922,11 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_width_calc(mut self, calc: Calc) -> Self {
+        self.style.width = calc.into();
+        self
+    }
+
     pub fn with_max_width(mut self, max: impl Into<Unit>) -> Self {
         self.style.max_width = max.into().into();
         self
@@ -585,6 +937,11 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_max_width_calc(mut self, calc: Calc) -> Self {
+        self.style.max_width = calc.into();
+        self
+    }
+
     pub fn with_min_width(mut self, min: impl Into<Unit>) -> Self {
         self.style.min_width = min.into().into();
         self
@@ -595,6 +952,11 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_min_width_calc(mut self, calc: Calc) -> Self {
+        self.style.min_width = calc.into();
+        self
+    }
+
     pub fn with_height(mut self, height: impl Into<Unit>) -> Self {
         self.style.height = height.into().into();
         self
@@ -605,6 +967,11 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_height_calc(mut self, calc: Calc) -> Self {
+        self.style.height = calc.into();
+        self
+    }
+
     pub fn with_max_height(mut self, max: impl Into<Unit>) -> Self {
         self.style.max_height = max.into().into();
         self
@@ -615,6 +982,11 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_max_height_calc(mut self, calc: Calc) -> Self {
+        self.style.max_height = calc.into();
+        self
+    }
+
     pub fn with_min_height(mut self, min: impl Into<Unit>) -> Self {
         self.style.min_height = min.into().into();
         self
@@ -625,6 +997,21 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_min_height_calc(mut self, calc: Calc) -> Self {
+        self.style.min_height = calc.into();
+        self
+    }
+
+    pub fn with_aspect_ratio(mut self, width: impl Into<Fill>, height: impl Into<Fill>) -> Self {
+        self.style.aspect_ratio = Some((width.into(), height.into()));
+        self
+    }
+
+    pub fn with_basis(mut self, basis: Basis) -> Self {
+        self.style.basis = Some(basis);
+        self
+    }
+
     pub fn with_grow(mut self, grow: impl Into<Fill>) -> Self {
         self.style.grow = Some(grow.into());
         self
@@ -645,11 +1032,76 @@ impl StyleBuilder {
         self
     }
 
+    pub fn with_border_box(mut self, border_box: bool) -> Self {
+        self.style.border_box = Some(border_box);
+        self
+    }
+
     pub fn with_padding(mut self, padding: impl Into<Quad>) -> Self {
         self.style.padding = padding.into();
         self
     }
 
+    pub fn with_padding_calc(mut self, padding: CalcQuad) -> Self {
+        self.style.padding_calc = Some(padding);
+        self
+    }
+
+    pub fn with_margin(mut self, margin: impl Into<Quad>) -> Self {
+        self.style.margin = margin.into();
+        self
+    }
+
+    pub fn with_collapse_margins(mut self, collapse: bool) -> Self {
+        self.style.collapse_margins = Some(collapse);
+        self
+    }
+
+    pub fn with_float(mut self, float: Float) -> Self {
+        self.style.float = Some(float);
+        self
+    }
+
+    pub fn with_clear(mut self, clear: Clear) -> Self {
+        self.style.clear = Some(clear);
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.style.overflow = Some(overflow);
+        self
+    }
+
+    pub fn with_widows(mut self, lines: usize) -> Self {
+        self.style.widows = Some(lines);
+        self
+    }
+
+    pub fn with_orphans(mut self, lines: usize) -> Self {
+        self.style.orphans = Some(lines);
+        self
+    }
+
+    pub fn with_break_before(mut self, page_break: BreakBetween) -> Self {
+        self.style.break_before = Some(page_break);
+        self
+    }
+
+    pub fn with_break_after(mut self, page_break: BreakBetween) -> Self {
+        self.style.break_after = Some(page_break);
+        self
+    }
+
+    pub fn with_break_inside(mut self, page_break: BreakInside) -> Self {
+        self.style.break_inside = Some(page_break);
+        self
+    }
+
+    pub fn with_keep_with_next(mut self, keep: bool) -> Self {
+        self.style.keep_with_next = Some(keep);
+        self
+    }
+
     pub fn with_align_items(mut self, align_items: AlignItems) -> Self {
         self.style.align_items = Some(align_items);
         self
@@ -0,0 +1,634 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    Border, BreakBetween, BreakInside, Error, Footnote, GridArea, GridBox, Layout, MeasureContext,
+    Position, RenderContext, Stroke, Style, Styled, Track,
+    position::{Offset, Size},
+    unit::Unit,
+};
+
+pub struct TableCell {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    content: Box<dyn Layout>,
+    row_span: usize,
+    column_span: usize,
+}
+
+impl TableCell {
+    pub fn new(content: impl Layout + 'static) -> Self {
+        Self::new_dyn(Box::new(content))
+    }
+
+    pub fn new_dyn(content: Box<dyn Layout>) -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::none(),
+            style: Style::new(),
+            content,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn rowspan(mut self, span: usize) -> Self {
+        self.row_span = span.max(1);
+        self
+    }
+
+    pub fn colspan(mut self, span: usize) -> Self {
+        self.column_span = span.max(1);
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(&self.style));
+        self
+    }
+}
+
+impl Position for TableCell {
+    fn element(&self) -> &str {
+        "TableCell"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &self.size
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+}
+
+impl Styled for TableCell {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.content
+            .set_style(self.content.style_ref().inherit(&style));
+        self.size.apply_style(crate::Axis::Horizontal, &style);
+        self.style = style;
+    }
+}
+
+impl Layout for TableCell {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, mut room: Size) -> Result<(), Error> {
+        // calc expressions refer to parent size and font size
+        let font_size = self.style.font_size(ctx);
+        if let Some(style) = self.style.resolve_padding(room.base_width(), font_size) {
+            self.style = style;
+        }
+
+        self.size
+            .width
+            .resolve_parented(room.base_width(), font_size);
+        self.size
+            .height
+            .resolve_parented(room.base_height(), font_size);
+
+        self.style_ref().insets().narrow(None, Some(&mut room));
+        self.content.measure(ctx, room)?;
+
+        let mut content_size = self
+            .content
+            .size_after_wrap_ref()
+            .cloned()
+            .unwrap_or_else(Size::zero);
+        self.style_ref()
+            .insets()
+            .widen(None, Some(&mut content_size));
+
+        self.size.width.resolve_content(content_size.base_width());
+        self.size.height.resolve_content(content_size.base_height());
+
+        Ok(())
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        let mut size = self.content.min_content_size(ctx)?;
+        self.style_ref().insets().widen(None, Some(&mut size));
+        Ok(self.size.definite_or(&size))
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        let mut size = self.content.max_content_size(ctx)?;
+        self.style_ref().insets().widen(None, Some(&mut size));
+        Ok(self.size.definite_or(&size))
+    }
+
+    // Cell always covers the whole grid area, so that collapsed borders meet.
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        offset: Offset,
+        size: Size,
+    ) -> Result<(), Error> {
+        let mut content_offset = offset.clone();
+        let mut room = size.clone();
+        self.style_ref()
+            .insets()
+            .narrow(Some(&mut content_offset), Some(&mut room));
+
+        self.content.lay_out(ctx, content_offset, room)?;
+
+        self.offset = offset;
+        self.size = size;
+
+        Ok(())
+    }
+
+    // Borders are drawn by the table, see Table::render_borders.
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        self.content.render(ctx)?;
+        ctx.debug_frame(self.offset(), self.size());
+        Ok(())
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.content.footnotes_mut()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
+        Box::new(std::iter::once(&self.content))
+    }
+}
+
+#[derive(Default)]
+pub struct TableRow {
+    cells: Vec<TableCell>,
+}
+
+impl TableRow {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn cell(self, content: impl Layout + 'static) -> Self {
+        self.table_cell(TableCell::new(content))
+    }
+
+    pub fn table_cell(mut self, cell: TableCell) -> Self {
+        self.cells.push(cell);
+        self
+    }
+}
+
+type RowFactory = Arc<dyn Fn() -> TableRow + Send + Sync>;
+
+pub struct Table {
+    grid: GridBox,
+    header_rows: usize,
+    body_rows: usize,
+    footer_rows: usize,
+    cell_border: Border,
+    repeat_header: bool,
+    // header rows created anew for parts of the table split between pages
+    repeated_header: Vec<RowFactory>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self {
+            grid: GridBox::new(),
+            header_rows: 0,
+            body_rows: 0,
+            footer_rows: 0,
+            cell_border: Border::none(),
+            repeat_header: true,
+            repeated_header: vec![],
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.grid = self.grid.mark(mark);
+        self
+    }
+
+    pub fn columns<IT>(mut self, tracks: impl IntoIterator<Item = IT>) -> Self
+    where
+        IT: Into<Track>,
+    {
+        self.grid = self.grid.columns(tracks);
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(self.style_ref()));
+        self
+    }
+
+    // Border used by cells which do not define their own.
+    pub fn cell_border(mut self, border: impl Into<Border>) -> Self {
+        self.cell_border = border.into();
+        self
+    }
+
+    // Whether parts split off by pagination start with the repeated_header rows, on by default.
+    pub fn repeat_header(mut self, repeat: bool) -> Self {
+        self.repeat_header = repeat;
+        self
+    }
+
+    // Header row above the body of the first part only, see Table::repeated_header.
+    pub fn header(mut self, row: TableRow) -> Self {
+        let at = self.header_rows;
+        self.header_rows += 1;
+        self.insert_row(at, row)
+    }
+
+    // Header row, which parts of the table split by pagination repeat, see Table::split_off.
+    pub fn repeated_header(mut self, row: impl Fn() -> TableRow + Send + Sync + 'static) -> Self {
+        let row: RowFactory = Arc::new(row);
+        self.repeated_header.push(row.clone());
+        self.header(row())
+    }
+
+    pub fn row(mut self, row: TableRow) -> Self {
+        let at = self.header_rows + self.body_rows;
+        self.body_rows += 1;
+        self.insert_row(at, row)
+    }
+
+    pub fn rows(self, rows: impl IntoIterator<Item = TableRow>) -> Self {
+        rows.into_iter().fold(self, |table, row| table.row(row))
+    }
+
+    pub fn footer(mut self, row: TableRow) -> Self {
+        let at = self.header_rows + self.body_rows + self.footer_rows;
+        self.footer_rows += 1;
+        self.insert_row(at, row)
+    }
+
+    pub fn header_rows(&self) -> usize {
+        self.header_rows
+    }
+
+    pub fn body_rows(&self) -> usize {
+        self.body_rows
+    }
+
+    pub fn footer_rows(&self) -> usize {
+        self.footer_rows
+    }
+
+    pub fn grid(&self) -> &GridBox {
+        &self.grid
+    }
+
+    fn insert_row(mut self, at: usize, row: TableRow) -> Self {
+        self.grid.insert_rows(at, 1);
+
+        let mut column = 0;
+        for cell in row.cells {
+            // skip columns covered by row spans from rows above
+            while self
+                .grid
+                .areas()
+                .iter()
+                .any(|area| area.contains(at, column))
+            {
+                column += 1;
+            }
+
+            let area = GridArea::new(at, column).with_span(cell.row_span, cell.column_span);
+            column += cell.column_span;
+            self.grid = self.grid.child_area_dyn(area, Box::new(cell));
+        }
+
+        self
+    }
+
+    fn cell_at(&self, row: usize, column: usize) -> Option<&dyn Layout> {
+        self.grid
+            .iter()
+            .zip(self.grid.areas())
+            .find(|(_, area)| area.contains(row, column))
+            .map(|(cell, _)| cell.as_ref())
+    }
+
+    // Body and footer rows, which the table can break before, i.e. no cell spans over the break.
+    fn break_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        let rows = self.grid.row_tracks().len();
+        (self.header_rows + 1..rows).filter(|row| {
+            !self
+                .grid
+                .areas()
+                .iter()
+                .any(|area| area.row < *row && *row < area.row_end())
+        })
+    }
+
+    fn row_top(&self, row: usize) -> Unit {
+        self.grid.row_tracks()[row].0 - self.offset().y
+    }
+
+    fn cell_border_of(&self, cell: &dyn Layout) -> Border {
+        cell.style_ref().border().merge(&self.cell_border)
+    }
+
+    // Collapsed borders: every edge is drawn once, the thicker of both neighbouring strokes wins.
+    // Outer edges collapse with the border of the table itself.
+    fn render_borders(&self, ctx: &mut dyn RenderContext, cell: &dyn Layout, area: &GridArea) {
+        let rows = self.grid.row_tracks().len();
+        let columns = self.grid.column_tracks().len();
+        let table = self.style_ref().border();
+        let own = self.cell_border_of(cell);
+
+        let top_left = cell.offset();
+        let bottom_right = top_left + cell.size();
+
+        // edges of spanning cells collapse with every neighbour along them, segment by segment
+        let column_tracks = self.grid.column_tracks();
+        let row_tracks = self.grid.row_tracks();
+        let x_segments = segments(
+            column_tracks,
+            area.column..area.column_end(),
+            top_left.x,
+            bottom_right.x,
+        );
+        let y_segments = segments(
+            row_tracks,
+            area.row..area.row_end(),
+            top_left.y,
+            bottom_right.y,
+        );
+
+        for (column, left_x, right_x) in x_segments.iter().copied() {
+            let above = (area.row > 0)
+                .then(|| self.cell_at(area.row - 1, column))
+                .flatten()
+                .map(|cell| self.cell_border_of(cell));
+            let top = if area.row == 0 {
+                thicker(own.top(), table.top())
+            } else {
+                thicker(own.top(), above.as_ref().and_then(Border::bottom))
+            };
+            if let Some(stroke) = top {
+                ctx.line(
+                    &Offset::new(left_x, top_left.y),
+                    &Offset::new(right_x, top_left.y),
+                    stroke,
+                );
+            }
+
+            // bottom edges are drawn by the neighbour, if there is any
+            if self.cell_at(area.row_end(), column).is_none() {
+                let bottom = if area.row_end() >= rows {
+                    thicker(own.bottom(), table.bottom())
+                } else {
+                    own.bottom()
+                };
+                if let Some(stroke) = bottom {
+                    ctx.line(
+                        &Offset::new(right_x, bottom_right.y),
+                        &Offset::new(left_x, bottom_right.y),
+                        stroke,
+                    );
+                }
+            }
+        }
+
+        for (row, top_y, bottom_y) in y_segments.iter().copied() {
+            let before = (area.column > 0)
+                .then(|| self.cell_at(row, area.column - 1))
+                .flatten()
+                .map(|cell| self.cell_border_of(cell));
+            let left = if area.column == 0 {
+                thicker(own.left(), table.left())
+            } else {
+                thicker(own.left(), before.as_ref().and_then(Border::right))
+            };
+            if let Some(stroke) = left {
+                ctx.line(
+                    &Offset::new(top_left.x, bottom_y),
+                    &Offset::new(top_left.x, top_y),
+                    stroke,
+                );
+            }
+
+            // right edges are drawn by the neighbour, if there is any
+            if self.cell_at(row, area.column_end()).is_none() {
+                let right = if area.column_end() >= columns {
+                    thicker(own.right(), table.right())
+                } else {
+                    own.right()
+                };
+                if let Some(stroke) = right {
+                    ctx.line(
+                        &Offset::new(bottom_right.x, top_y),
+                        &Offset::new(bottom_right.x, bottom_y),
+                        stroke,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Track indexes with their start and end along a cell edge, which spans from start to end.
+fn segments(
+    tracks: &[(Unit, Unit)],
+    spanned: Range<usize>,
+    start: Unit,
+    end: Unit,
+) -> Vec<(usize, Unit, Unit)> {
+    let last = spanned.end.min(tracks.len()).max(spanned.start + 1) - 1;
+    spanned
+        .clone()
+        .take_while(|index| *index <= last)
+        .map(|index| {
+            let from = if index == spanned.start {
+                start
+            } else {
+                tracks[index].0
+            };
+            let to = if index == last {
+                end
+            } else {
+                tracks[index + 1].0
+            };
+            (index, from, to)
+        })
+        .collect()
+}
+
+fn thicker<'s>(l: Option<&'s Stroke>, r: Option<&'s Stroke>) -> Option<&'s Stroke> {
+    match (l, r) {
+        (Some(l), Some(r)) if r.thickness().0 > l.thickness().0 => Some(r),
+        (None, r) => r,
+        (l, _) => l,
+    }
+}
+
+impl Position for Table {
+    fn element(&self) -> &str {
+        "Table"
+    }
+
+    fn mark(&self) -> &str {
+        Position::mark(&self.grid)
+    }
+
+    fn offset(&self) -> &Offset {
+        self.grid.offset()
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        self.grid.offset_mut()
+    }
+
+    fn size(&self) -> &Size {
+        self.grid.size()
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        self.grid.size_mut()
+    }
+
+    fn content_size(&self) -> Option<&Size> {
+        self.grid.content_size()
+    }
+}
+
+impl Styled for Table {
+    fn style_ref(&self) -> &Style {
+        self.grid.style_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.grid.set_style(style);
+    }
+}
+
+impl Layout for Table {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, room: Size) -> Result<(), Error> {
+        self.grid.measure(ctx, room)
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.grid.min_content_size(ctx)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.grid.max_content_size(ctx)
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        offset: Offset,
+        room: Size,
+    ) -> Result<(), Error> {
+        self.grid.lay_out(ctx, offset, room)
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        for (cell, area) in self.grid.iter().zip(self.grid.areas()) {
+            cell.render(ctx)?;
+            self.render_borders(ctx, cell.as_ref(), area);
+        }
+        ctx.debug_frame(self.offset(), self.size());
+
+        Ok(())
+    }
+
+    // Tops of body and footer rows, header rows stay together with the first body row.
+    fn breaks(&self) -> Vec<Unit> {
+        if matches!(self.style_ref().break_inside(), BreakInside::Avoid) {
+            return vec![];
+        }
+        self.break_rows().map(|row| self.row_top(row)).collect()
+    }
+
+    // Breaks forced by styles of cells starting at a row.
+    fn forced_breaks(&self) -> Vec<(Unit, BreakBetween)> {
+        self.break_rows()
+            .filter_map(|row| {
+                self.grid
+                    .iter()
+                    .zip(self.grid.areas())
+                    .filter(|(_, area)| area.row == row)
+                    .map(|(cell, _)| cell.style_ref().break_before())
+                    .find(BreakBetween::is_forced)
+                    .map(|between| (self.row_top(row), between))
+            })
+            .collect()
+    }
+
+    // Rows from the break go into a new table of the same columns and style, which starts with
+    // the repeated header rows, if the header repeats.
+    fn split_off(&mut self, at: Unit) -> Option<Box<dyn Layout>> {
+        let row = self
+            .break_rows()
+            .filter(|row| self.row_top(*row) <= at)
+            .max()?;
+
+        let head_body = (row - self.header_rows).min(self.body_rows);
+        let head_footer = row - self.header_rows - head_body;
+        let mut tail = Table {
+            grid: self.grid.split_rows_off(row),
+            header_rows: 0,
+            body_rows: self.body_rows - head_body,
+            footer_rows: self.footer_rows - head_footer,
+            cell_border: self.cell_border.clone(),
+            repeat_header: self.repeat_header,
+            repeated_header: self.repeated_header.clone(),
+        };
+        self.body_rows = head_body;
+        self.footer_rows = head_footer;
+
+        if self.repeat_header {
+            for row in self.repeated_header.iter() {
+                tail = tail.header(row());
+            }
+        }
+        Some(Box::new(tail))
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.grid.footnotes_mut()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
+        self.grid.iter()
+    }
+}
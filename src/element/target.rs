@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use crate::{
+    Error, Layout, MeasureContext, Position, Style, Styled,
+    position::{Offset, Size},
+};
+
+// Heading of a target, the table of contents lists it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: usize,
+    pub title: String,
+}
+
+// Place in the flow, which references resolve to the page and label of, see Targets.
+pub struct Target {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    id: String,
+    label: Option<String>,
+    heading: Option<Heading>,
+}
+
+impl Target {
+    pub fn new(id: impl ToString) -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::none(),
+            style: Style::new(),
+            id: id.to_string(),
+            label: None,
+            heading: None,
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    // Label, e.g. a clause number, references show instead of the page number.
+    pub fn label(mut self, label: impl ToString) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    // Levels start at one for top headings.
+    pub fn heading(mut self, level: usize, title: impl ToString) -> Self {
+        self.heading = Some(Heading {
+            level,
+            title: title.to_string(),
+        });
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn label_ref(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn heading_ref(&self) -> Option<&Heading> {
+        self.heading.as_ref()
+    }
+}
+
+impl Position for Target {
+    fn element(&self) -> &str {
+        "Target"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &Size::NONE
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    fn size_after_wrap_ref(&self) -> Option<&Size> {
+        None
+    }
+
+    fn size_after_lay_out(&self) -> Option<Size> {
+        None
+    }
+}
+
+impl Styled for Target {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.style = style;
+    }
+}
+
+impl Layout for Target {
+    fn lay_out(
+        &mut self,
+        _: &mut dyn MeasureContext,
+        position: Offset,
+        _: Size,
+    ) -> Result<(), Error> {
+        self.offset = position;
+        Ok(())
+    }
+
+    fn target(&self) -> Option<&Target> {
+        Some(self)
+    }
+}
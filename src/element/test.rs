@@ -3,13 +3,29 @@ use std::sync::{Arc, OnceLock};
 use crate::{
     Error, GlyphPosition, MeasureContext, NewPageOptions, RenderContext, Stroke, Style,
     TextPosition,
-    position::{Offset, Size},
-    unit::Em,
+    position::{Affine, Offset, Size},
+    unit::{Em, Unit},
 };
 
+pub(crate) mod anchor;
+pub(crate) mod aspect_ratio;
 pub(crate) mod baseline;
+pub(crate) mod border;
+pub(crate) mod calc;
+pub(crate) mod columns;
+pub(crate) mod flex_in_hbox;
+pub(crate) mod float;
+pub(crate) mod gridbox;
 pub(crate) mod hbox_in_hbox;
 pub(crate) mod hbox_in_vbox;
+pub(crate) mod intrinsic;
+pub(crate) mod margin;
+pub(crate) mod overflow;
+pub(crate) mod page_template;
+pub(crate) mod pagination;
+pub(crate) mod stack;
+pub(crate) mod table;
+pub(crate) mod transform;
 pub(crate) mod various;
 pub(crate) mod vbox_in_hbox;
 pub(crate) mod vbox_in_vbox;
@@ -18,7 +34,7 @@ pub(crate) struct Ctx;
 
 impl MeasureContext for Ctx {
     fn style(&self) -> &Style {
-        todo!()
+        STYLE.get_or_init(Style::new)
     }
 
     fn typeset(&mut self, _: &Style, _: &str) -> Result<TextPosition, crate::Error> {
@@ -55,6 +71,22 @@ impl RenderContext for usize {
         todo!()
     }
 
+    fn push_transform(&mut self, _: &Affine) {
+        todo!()
+    }
+
+    fn pop_transform(&mut self) {
+        todo!()
+    }
+
+    fn push_clip(&mut self, _: &Offset, _: &Size) {
+        todo!()
+    }
+
+    fn pop_clip(&mut self) {
+        todo!()
+    }
+
     fn debug_frame(&mut self, _: &Offset, _: &Size) {
         todo!()
     }
@@ -77,3 +109,95 @@ impl RenderContext for usize {
         todo!()
     }
 }
+
+// Render context recording what is rendered, text is typeset like by the usize context.
+pub(crate) struct Recorder {
+    // number of the current page, counted by new pages
+    pub(crate) page: usize,
+    // debug frames at x and y, with their width and height
+    pub(crate) frames: Vec<(i64, i64, i64, i64)>,
+    // lines from x and y to x and y, with their thickness
+    pub(crate) lines: Vec<(i64, i64, i64, i64, i64)>,
+    // pushes and pops of transforms and clips, in render order
+    pub(crate) calls: Vec<String>,
+}
+
+impl Recorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            page: 1,
+            frames: vec![],
+            lines: vec![],
+            calls: vec![],
+        }
+    }
+}
+
+impl MeasureContext for Recorder {
+    fn style(&self) -> &Style {
+        0_usize.style()
+    }
+
+    fn typeset(&mut self, style: &Style, text: &str) -> Result<TextPosition, Error> {
+        0_usize.typeset(style, text)
+    }
+}
+
+impl RenderContext for Recorder {
+    fn debug_frame(&mut self, offset: &Offset, size: &Size) {
+        self.frames.push((
+            offset.x.0,
+            offset.y.0,
+            size.base_width().0,
+            size.base_height().0,
+        ));
+    }
+
+    fn check_page_break(&mut self, _: Unit, _: Unit, _: bool) -> bool {
+        false
+    }
+
+    fn release_page_break_reservation(&mut self) {}
+
+    fn new_page(&mut self, _: Option<NewPageOptions>) {
+        self.page += 1;
+    }
+
+    fn page_number(&self) -> Option<usize> {
+        Some(self.page)
+    }
+
+    fn push_transform(&mut self, transform: &Affine) {
+        let Affine { a, b, c, d, e, f } = transform;
+        self.calls
+            .push(format!("transform {a} {b} {c} {d} {} {}", e.0, f.0));
+    }
+
+    fn pop_transform(&mut self) {
+        self.calls.push("pop transform".to_string());
+    }
+
+    fn push_clip(&mut self, offset: &Offset, size: &Size) {
+        self.calls.push(format!(
+            "clip {} {} {} {}",
+            offset.x.0,
+            offset.y.0,
+            size.base_width().0,
+            size.base_height().0
+        ));
+    }
+
+    fn pop_clip(&mut self) {
+        self.calls.push("pop clip".to_string());
+    }
+
+    fn image(&mut self, _: &Offset, _: &Offset, _: image::DynamicImage) {}
+
+    fn line(&mut self, from: &Offset, to: &Offset, stroke: &Stroke) {
+        let thickness = Unit::from(stroke.thickness()).0;
+        self.lines
+            .push((from.x.0, from.y.0, to.x.0, to.y.0, thickness));
+    }
+
+    fn text(&mut self, _: &Offset, _: &Style, _: &TextPosition, _: bool) {}
+}
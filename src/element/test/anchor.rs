@@ -0,0 +1,107 @@
+use crate::{
+    Anchor, Layout, StyleBuilder, bbox, hbox,
+    position::{Offset, Quad, Size},
+};
+
+use super::Recorder;
+
+#[test]
+fn anchors_resolve_against_content() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = bbox(Offset::zero())
+        .style(
+            StyleBuilder::new()
+                .with_width(100)
+                .with_height(80)
+                .with_padding(Quad::square(5)),
+        )
+        .child(hbox().axis_size(10).cross_size(10))
+        .child_anchored(
+            Anchor::new().right(4).bottom(6),
+            hbox().axis_size(20).cross_size(10),
+        )
+        .child_anchored(Anchor::new().center(), hbox().axis_size(30).cross_size(20))
+        .child_anchored(
+            Anchor::new().left(10).right(10).top(2),
+            hbox().cross_size(10),
+        );
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    let mut iter = outer.iter();
+    let plain = iter.next().unwrap();
+    assert_eq!(Offset::new(15, 15), *plain.offset());
+
+    let corner = iter.next().unwrap();
+    assert_eq!(Offset::new(81, 69), *corner.offset());
+
+    let centered = iter.next().unwrap();
+    assert_eq!(Offset::new(45, 40), *centered.offset());
+
+    let stretched = iter.next().unwrap();
+    assert_eq!(Offset::new(25, 17), *stretched.offset());
+    assert_eq!(70, stretched.size().base_width().0);
+    assert_eq!(10, stretched.size().base_height().0);
+}
+
+#[test]
+fn anchors_keep_own_offset_in_free_axis() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = bbox(Offset::zero())
+        .style(StyleBuilder::new().with_width(100).with_height(80))
+        .child_anchored(
+            Anchor::new().bottom(0).z_index(-1),
+            bbox(Offset::new(7, 3)).style(StyleBuilder::new().with_width(10).with_height(10)),
+        );
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    let child = outer.iter().next().unwrap();
+    assert_eq!(Offset::new(7, 70), *child.offset());
+}
+
+#[test]
+fn overlapping_anchors_paint_by_z_index() {
+    let ctx = &mut Recorder::new();
+
+    let mut outer = bbox(Offset::zero())
+        .style(StyleBuilder::new().with_width(100).with_height(80))
+        .child_anchored(
+            Anchor::new().left(0).top(0).z_index(2),
+            hbox().axis_size(10).cross_size(10),
+        )
+        .child_anchored(
+            Anchor::new().left(0).top(0).z_index(-1),
+            hbox().axis_size(20).cross_size(10),
+        )
+        .child_anchored(
+            Anchor::new().left(0).top(0),
+            hbox().axis_size(30).cross_size(10),
+        )
+        .child_anchored(
+            Anchor::new().left(0).top(0),
+            hbox().axis_size(40).cross_size(10),
+        );
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+    outer.render(ctx).unwrap();
+
+    // equal z-indexes keep the order of children
+    let widths = ctx
+        .frames
+        .iter()
+        .map(|(_, _, width, _)| *width)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![20, 30, 40, 10, 100], widths);
+}
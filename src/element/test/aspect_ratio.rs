@@ -0,0 +1,74 @@
+use crate::{
+    Layout, Position, StyleBuilder, hbox,
+    position::{Offset, Size},
+    vbox,
+};
+
+fn lay_out(outer: &mut impl Layout) {
+    let ctx = &mut 0_usize;
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+}
+
+#[test]
+fn width_drives_height() {
+    let cell = hbox()
+        .axis_size(40)
+        .style(StyleBuilder::new().with_aspect_ratio(2, 1));
+    let mut outer = vbox().child(cell);
+
+    lay_out(&mut outer);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(40, cell.size().base_width().0);
+    assert_eq!(20, cell.size().base_height().0);
+    assert_eq!(20, outer.size().base_height().0);
+}
+
+#[test]
+fn height_drives_width() {
+    let cell = vbox()
+        .axis_size(30)
+        .style(StyleBuilder::new().with_aspect_ratio(2, 1));
+    let mut outer = hbox().child(cell);
+
+    lay_out(&mut outer);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(60, cell.size().base_width().0);
+    assert_eq!(30, cell.size().base_height().0);
+    assert_eq!(60, outer.size().base_width().0);
+}
+
+#[test]
+fn max_of_derived_feeds_back() {
+    let cell = hbox().axis_size(40).style(
+        StyleBuilder::new()
+            .with_aspect_ratio(2, 1)
+            .with_max_height(10),
+    );
+    let mut outer = vbox().child(cell);
+
+    lay_out(&mut outer);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(20, cell.size().base_width().0);
+    assert_eq!(10, cell.size().base_height().0);
+}
+
+#[test]
+fn grown_width_drives_height() {
+    let cell = hbox()
+        .axis_size(10)
+        .axis_grow(1)
+        .style(StyleBuilder::new().with_aspect_ratio(2, 1));
+    let mut outer = hbox().axis_size(100).child(cell);
+
+    lay_out(&mut outer);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(100, cell.size().base_width().0);
+    assert_eq!(50, cell.size().base_height().0);
+}
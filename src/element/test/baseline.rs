@@ -1,5 +1,5 @@
 use crate::{
-    AlignItems, Layout, Position, StyleBuilder,
+    AlignItems, Layout, LayoutBox, Position, StyleBuilder, hbox,
     position::{Offset, Size},
     test::Ctx,
     vbox,
@@ -132,3 +132,36 @@ fn self_baseline_inherits_and_positions() {
     assert_eq!(100, box1.size().base_height().0);
     assert_eq!(85, box1.size().depth().unwrap().0);
 }
+
+fn line() -> LayoutBox {
+    hbox().axis_size(30).cross_size(10).axis_depth(2)
+}
+
+#[test]
+fn last_baseline_aligns_last_lines() {
+    let address = vbox().child(line()).child(line()).child(line());
+
+    let mut outer = hbox()
+        .style(StyleBuilder::new().with_align_items(AlignItems::LastBaseline))
+        .child(address)
+        .child(line());
+
+    outer.measure(&mut 0_usize, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(&mut 0_usize, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(60, outer.size().base_width().0);
+    assert_eq!(30, outer.size().base_height().0);
+    assert_eq!(2, outer.size().last_depth().unwrap().0);
+
+    let mut iter = outer.iter();
+    let address = iter.next().unwrap();
+    assert_eq!(10, address.offset().y.0);
+    assert_eq!(30, address.size().base_height().0);
+    assert_eq!(2, address.size().last_depth().unwrap().0);
+
+    let total = iter.next().unwrap();
+    assert_eq!(40, total.offset().x.0);
+    assert_eq!(30, total.offset().y.0);
+}
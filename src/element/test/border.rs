@@ -0,0 +1,57 @@
+use crate::{
+    Border, Layout, Position, Rgba, Stroke, StyleBuilder, Styled,
+    position::{Offset, Size},
+    unit::Unit,
+    vbox,
+};
+
+fn bordered(border_box: bool) -> StyleBuilder {
+    StyleBuilder::new()
+        .with_border(Border::square(Stroke::new(Rgba::black(), Unit(2000))))
+        .with_border_box(border_box)
+}
+
+#[test]
+fn border_lines_take_no_space() {
+    let ctx = &mut 0_usize;
+
+    let cell = vbox().axis_size(10000).cross_size(20000);
+    let mut outer = vbox().style(bordered(false)).child(cell);
+
+    outer.measure(ctx, Size::fixed(190000, 277000)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10000, 10000), Size::fixed(190000, 277000))
+        .unwrap();
+
+    assert_eq!(20000, outer.size().base_width().0);
+    assert_eq!(10000, outer.size().base_height().0);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(10000, cell.offset().x.0);
+    assert_eq!(10000, cell.offset().y.0);
+}
+
+#[test]
+fn border_widths_in_border_box() {
+    let ctx = &mut 0_usize;
+
+    let cell = vbox().axis_size(10000).cross_size(20000);
+    let mut outer = vbox().style(bordered(true)).child(cell);
+
+    outer.measure(ctx, Size::fixed(190000, 277000)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10000, 10000), Size::fixed(190000, 277000))
+        .unwrap();
+
+    assert_eq!(24000, outer.size().base_width().0);
+    assert_eq!(14000, outer.size().base_height().0);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(12000, cell.offset().x.0);
+    assert_eq!(12000, cell.offset().y.0);
+
+    // lines are centred inside the border area
+    let (top_left, bottom_right) = outer.style_ref().border_frame(outer.offset(), outer.size());
+    assert_eq!(Offset::new(11000, 11000), top_left);
+    assert_eq!(Offset::new(33000, 23000), bottom_right);
+}
@@ -0,0 +1,72 @@
+use crate::{
+    Calc, Layout, Position, StyleBuilder, hbox,
+    position::{CalcQuad, Offset, Size},
+    table, tcell, trow,
+    unit::{Pt, Unit},
+    vbox,
+};
+
+#[test]
+fn calc_width_and_padding() {
+    let ctx = &mut 0_usize;
+
+    let cell = hbox().axis_size(20).cross_size(10);
+    let mut outer = vbox()
+        .style(
+            StyleBuilder::new()
+                .with_width_calc(Calc::new().parent(500).fixed(-5))
+                .with_padding_calc(CalcQuad::h_v(Calc::new().parent(100), Calc::new().fixed(3))),
+        )
+        .child(cell);
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(90, outer.size().base_width().0);
+    assert_eq!(16, outer.size().base_height().0);
+
+    let cell = outer.iter().next().unwrap();
+    assert_eq!(29, cell.offset().x.0);
+    assert_eq!(13, cell.offset().y.0);
+}
+
+#[test]
+fn em_refers_to_font_size_at_measure() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = hbox()
+        .style(
+            StyleBuilder::new()
+                .with_width_calc(Calc::new().fixed(5).em_per_mille(2000))
+                .with_font_size(Pt(10.0)),
+        )
+        .add_style(StyleBuilder::new().with_font_size(Pt(20.0)));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    // two em of the font size added last
+    let em = Unit::from(Pt(20.0)).0;
+    assert_eq!(5 + 2 * em, outer.size().base_width().0);
+}
+
+#[test]
+fn calc_padding_of_table_cell() {
+    let ctx = &mut 0_usize;
+
+    let padded = StyleBuilder::new().with_padding_calc(CalcQuad::square(Calc::new().fixed(2)));
+    let mut table =
+        table().row(trow().table_cell(tcell(hbox().axis_size(10).cross_size(5)).style(padded)));
+
+    table.measure(ctx, Size::fixed(190, 277)).unwrap();
+    table
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(14, table.size().base_width().0);
+    assert_eq!(9, table.size().base_height().0);
+}
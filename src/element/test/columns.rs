@@ -0,0 +1,152 @@
+use crate::{
+    ColumnFill, ColumnFragment, Layout, Position, StyleBuilder, columns, hbox,
+    position::{Offset, Size},
+    unit::Unit,
+    vbox,
+};
+
+use super::Recorder;
+
+fn paragraph(words: usize) -> crate::LayoutBox {
+    (0..words).fold(hbox(), |paragraph, _| {
+        paragraph.child(hbox().axis_size(20).cross_size(10))
+    })
+}
+
+#[test]
+fn balanced_columns_split_paragraph() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = columns()
+        .width(100)
+        .column_count(2)
+        .style(StyleBuilder::new().with_horizontal_gap(10))
+        .child(paragraph(6))
+        .child(vbox().axis_size(10).cross_size(40));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(100, outer.size().base_width().0);
+    assert_eq!(20, outer.size().base_height().0);
+    assert_eq!(2, outer.column_tracks().len());
+    assert_eq!(
+        (65, 45),
+        (outer.column_tracks()[1].0.0, outer.column_tracks()[1].1.0)
+    );
+
+    let fragment = |child, column, from, to, x, y| ColumnFragment {
+        child,
+        column,
+        from: Unit(from),
+        to: Unit(to),
+        offset: Offset::new(x, y),
+    };
+    assert_eq!(
+        vec![
+            fragment(0, 0, 0, 20, 10, 10),
+            fragment(0, 1, 20, 30, 65, 10),
+            fragment(1, 1, 0, 10, 65, 20),
+        ],
+        outer.fragments()
+    );
+
+    // the paragraph stays whole at its first fragment
+    let mut iter = outer.iter();
+    let paragraph = iter.next().unwrap();
+    assert_eq!(Offset::new(10, 10), *paragraph.offset());
+    assert_eq!(30, paragraph.size().base_height().0);
+    assert_eq!(6, paragraph.iter().count());
+
+    let block = iter.next().unwrap();
+    assert_eq!(Offset::new(65, 20), *block.offset());
+}
+
+#[test]
+fn sequential_columns_by_width() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = columns()
+        .width(100)
+        .height(25)
+        .column_width(30)
+        .style(StyleBuilder::new().with_horizontal_gap(5))
+        .fill(ColumnFill::Sequential)
+        .children::<crate::LayoutBox, _, _>((0..3).map(|_| vbox().axis_size(10).cross_size(20)));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(25, outer.size().base_height().0);
+
+    // three columns of 30 fit, widened to fill the box
+    let offsets = outer
+        .iter()
+        .map(|child| (child.offset().x.0, child.offset().y.0))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(0, 0), (0, 10), (35, 0)], offsets);
+    assert_eq!(30, outer.column_tracks()[0].1.0);
+}
+
+#[test]
+fn repeated_lay_out_flows_again() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = columns()
+        .width(100)
+        .height(20)
+        .column_count(2)
+        .fill(ColumnFill::Sequential)
+        .child(paragraph(8));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+    let fragments = outer.fragments().to_vec();
+    assert_eq!(2, fragments.len());
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(fragments, outer.fragments());
+    assert_eq!(1, outer.iter().count());
+    assert_eq!(8, outer.iter().next().unwrap().iter().count());
+}
+
+#[test]
+fn fragments_render_moved_and_clipped() {
+    let ctx = &mut Recorder::new();
+
+    let mut outer = columns()
+        .width(100)
+        .column_count(2)
+        .style(StyleBuilder::new().with_horizontal_gap(10))
+        .child(paragraph(8));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+    outer.render(ctx).unwrap();
+
+    assert_eq!(
+        vec![
+            "clip 10 10 45 20",
+            "transform 1 0 0 1 0 0",
+            "pop transform",
+            "pop clip",
+            "clip 65 10 45 20",
+            "transform 1 0 0 1 55 -20",
+            "pop transform",
+            "pop clip",
+        ],
+        ctx.calls
+    );
+}
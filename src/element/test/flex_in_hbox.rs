@@ -0,0 +1,226 @@
+use crate::{
+    AlignItems, Basis, Layout, Position, StyleBuilder, hbox,
+    position::{Offset, Size},
+    test::Ctx,
+    unit::Unit,
+    vbox,
+};
+
+#[test]
+fn triple_grow_boxes_max_redistributed() {
+    let box1 = hbox().axis_size(15).axis_grow(1).axis_max(30);
+    let box2 = hbox().axis_size(18).axis_grow(1);
+    let box3 = hbox().axis_size(16).axis_grow(1);
+
+    let mut outer = hbox()
+        .style(StyleBuilder::new().with_align_items(AlignItems::Start))
+        .child(box1)
+        .child(box2)
+        .child(box3);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    assert_eq!(10, outer.offset().x.0);
+    assert_eq!(10, outer.offset().y.0);
+    assert_eq!(190, outer.size().base_width().0);
+    assert_eq!(190, outer.content_size().unwrap().base_width().0);
+
+    let mut iter = outer.iter();
+    let box1 = iter.next().unwrap();
+
+    assert_eq!(10, box1.offset().x.0);
+    assert_eq!(30, box1.size().base_width().0);
+
+    let box2 = iter.next().unwrap();
+
+    assert_eq!(40, box2.offset().x.0);
+    assert_eq!(81, box2.size().base_width().0);
+
+    let box3 = iter.next().unwrap();
+
+    assert_eq!(121, box3.offset().x.0);
+    assert_eq!(79, box3.size().base_width().0);
+}
+
+#[test]
+fn double_grow_boxes_max_redistributed_by_weight() {
+    let box1 = hbox().axis_size(10).axis_grow(1);
+    let box2 = hbox().axis_size(10).axis_grow(3).axis_max(40);
+
+    let mut outer = hbox()
+        .style(StyleBuilder::new().with_align_items(AlignItems::Start))
+        .child(box1)
+        .child(box2);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    assert_eq!(190, outer.size().base_width().0);
+
+    let mut iter = outer.iter();
+    let box1 = iter.next().unwrap();
+
+    assert_eq!(10, box1.offset().x.0);
+    assert_eq!(150, box1.size().base_width().0);
+
+    let box2 = iter.next().unwrap();
+
+    assert_eq!(160, box2.offset().x.0);
+    assert_eq!(40, box2.size().base_width().0);
+}
+
+#[test]
+fn triple_shrink_boxes_min_redistributed() {
+    let box1 = hbox().axis_size(100).axis_shrink(1).axis_min(90);
+    let box2 = hbox().axis_size(100).axis_shrink(1);
+    let box3 = hbox().axis_size(100).axis_shrink(1);
+
+    let mut outer = hbox()
+        .style(
+            StyleBuilder::new()
+                .with_align_items(AlignItems::Start)
+                .with_wrap(false),
+        )
+        .child(box1)
+        .child(box2)
+        .child(box3);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    assert_eq!(190, outer.size().base_width().0);
+
+    let mut iter = outer.iter();
+    let box1 = iter.next().unwrap();
+
+    assert_eq!(10, box1.offset().x.0);
+    assert_eq!(90, box1.size().base_width().0);
+
+    let box2 = iter.next().unwrap();
+
+    assert_eq!(100, box2.offset().x.0);
+    assert_eq!(50, box2.size().base_width().0);
+
+    let box3 = iter.next().unwrap();
+
+    assert_eq!(150, box3.offset().x.0);
+    assert_eq!(50, box3.size().base_width().0);
+}
+
+#[test]
+fn double_shrink_boxes_weighted_by_base() {
+    let box1 = hbox().axis_size(40).axis_shrink(1);
+    let box2 = hbox().axis_size(160).axis_shrink(1);
+
+    let mut outer = hbox()
+        .style(
+            StyleBuilder::new()
+                .with_align_items(AlignItems::Start)
+                .with_wrap(false),
+        )
+        .child(box1)
+        .child(box2);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    assert_eq!(190, outer.size().base_width().0);
+
+    let mut iter = outer.iter();
+    let box1 = iter.next().unwrap();
+
+    assert_eq!(10, box1.offset().x.0);
+    assert_eq!(38, box1.size().base_width().0);
+
+    let box2 = iter.next().unwrap();
+
+    assert_eq!(48, box2.offset().x.0);
+    assert_eq!(152, box2.size().base_width().0);
+}
+
+#[test]
+fn double_grow_boxes_zero_basis() {
+    let box1 = hbox().style(
+        StyleBuilder::new()
+            .with_width(50)
+            .with_basis(Basis::Fixed(Unit::zero()))
+            .with_grow(2),
+    );
+    let box2 = hbox().axis_size(40).axis_grow(1);
+
+    let mut outer = hbox()
+        .style(StyleBuilder::new().with_align_items(AlignItems::Start))
+        .child(box1)
+        .child(box2);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    assert_eq!(190, outer.size().base_width().0);
+
+    let mut iter = outer.iter();
+    let box1 = iter.next().unwrap();
+
+    assert_eq!(10, box1.offset().x.0);
+    assert_eq!(100, box1.size().base_width().0);
+
+    let box2 = iter.next().unwrap();
+
+    assert_eq!(110, box2.offset().x.0);
+    assert_eq!(90, box2.size().base_width().0);
+}
+
+#[test]
+fn double_grow_boxes_auto_basis() {
+    let box1 = hbox().style(
+        StyleBuilder::new()
+            .with_width(50)
+            .with_basis(Basis::Auto)
+            .with_grow(2),
+    );
+    let box2 = hbox().axis_size(40).axis_grow(1);
+
+    let mut outer = hbox()
+        .style(StyleBuilder::new().with_align_items(AlignItems::Start))
+        .child(box1)
+        .child(box2);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    let mut iter = outer.iter();
+    let box1 = iter.next().unwrap();
+
+    assert_eq!(10, box1.offset().x.0);
+    assert_eq!(117, box1.size().base_width().0);
+
+    let box2 = iter.next().unwrap();
+
+    assert_eq!(127, box2.offset().x.0);
+    assert_eq!(73, box2.size().base_width().0);
+}
+
+#[test]
+fn basis_applies_only_along_parent_axis() {
+    let inner = hbox()
+        .axis_size(50)
+        .style(StyleBuilder::new().with_basis(Basis::Fixed(Unit::zero())));
+
+    let mut outer = vbox()
+        .style(StyleBuilder::new().with_align_items(AlignItems::Start))
+        .child(inner);
+
+    let position = Offset::new(10, 10);
+    let size = Size::fixed(190, 277);
+    outer.lay_out(&mut Ctx, position, size).unwrap();
+
+    let inner = outer.iter().next().unwrap();
+    assert_eq!(50, inner.size().base_width().0);
+}
@@ -0,0 +1,105 @@
+use crate::{
+    Clear, Float, Layout, Position, StyleBuilder, hbox,
+    position::{Offset, Size},
+    vbox,
+};
+
+fn paragraph(words: usize) -> crate::LayoutBox {
+    (0..words).fold(hbox(), |paragraph, _| {
+        paragraph.child(hbox().axis_size(20).cross_size(10))
+    })
+}
+
+#[test]
+fn lines_flow_around_left_float() {
+    let ctx = &mut 0_usize;
+
+    let float = hbox()
+        .axis_size(30)
+        .cross_size(25)
+        .style(StyleBuilder::new().with_float(Float::Left));
+    let below = hbox()
+        .axis_size(100)
+        .cross_size(5)
+        .style(StyleBuilder::new().with_clear(Clear::Both));
+
+    let mut outer = vbox()
+        .cross_size(100)
+        .child(float)
+        .child(paragraph(10))
+        .child(below);
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(45, outer.size().base_height().0);
+
+    let mut iter = outer.iter();
+    let float = iter.next().unwrap();
+    assert_eq!(Offset::new(10, 10), *float.offset());
+
+    let paragraph = iter.next().unwrap();
+    assert_eq!(Offset::new(10, 10), *paragraph.offset());
+    assert_eq!(40, paragraph.size().base_height().0);
+
+    let offsets = paragraph
+        .iter()
+        .map(|word| (word.offset().x.0, word.offset().y.0))
+        .collect::<Vec<_>>();
+    assert_eq!((40, 10), offsets[0]);
+    assert_eq!((80, 10), offsets[2]);
+    assert_eq!((40, 20), offsets[3]);
+    assert_eq!((40, 30), offsets[6]);
+    assert_eq!((10, 40), offsets[9]);
+
+    let below = iter.next().unwrap();
+    assert_eq!(Offset::new(10, 50), *below.offset());
+}
+
+#[test]
+fn floats_stack_on_both_sides() {
+    let ctx = &mut 0_usize;
+
+    let left = hbox()
+        .axis_size(30)
+        .cross_size(20)
+        .style(StyleBuilder::new().with_float(Float::Left));
+    let right = hbox()
+        .axis_size(20)
+        .cross_size(40)
+        .style(StyleBuilder::new().with_float(Float::Right));
+    let block = vbox().axis_size(10).cross_size(20);
+    let cleared = vbox()
+        .axis_size(10)
+        .cross_size(20)
+        .style(StyleBuilder::new().with_clear(Clear::Left));
+    let below = vbox()
+        .axis_size(10)
+        .cross_size(20)
+        .style(StyleBuilder::new().with_clear(Clear::Right));
+
+    let mut outer = vbox()
+        .cross_size(100)
+        .child(left)
+        .child(right)
+        .child(block)
+        .child(cleared)
+        .child(below);
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    let mut iter = outer.iter();
+    assert_eq!(Offset::new(0, 0), *iter.next().unwrap().offset());
+    assert_eq!(Offset::new(80, 0), *iter.next().unwrap().offset());
+
+    // a box not breaking into lines moves aside
+    assert_eq!(Offset::new(30, 0), *iter.next().unwrap().offset());
+    assert_eq!(Offset::new(0, 20), *iter.next().unwrap().offset());
+    assert_eq!(Offset::new(0, 40), *iter.next().unwrap().offset());
+    assert_eq!(50, outer.size().base_height().0);
+}
@@ -0,0 +1,109 @@
+use crate::{
+    Layout, Position, StyleBuilder, Track, grid, hbox,
+    position::{Offset, Size},
+};
+
+fn cell(width: i64, height: i64) -> impl Layout {
+    hbox().axis_size(width).cross_size(height)
+}
+
+#[test]
+fn fixed_and_fraction_columns() {
+    let ctx = &mut 0_usize;
+
+    let mut grid = (0..6).fold(
+        grid()
+            .columns([Track::fixed(20), Track::fr(1), Track::fr(3)])
+            .style(
+                StyleBuilder::new()
+                    .with_horizontal_gap(4)
+                    .with_vertical_gap(2),
+            ),
+        |grid, _| grid.child(cell(5, 6)),
+    );
+
+    grid.measure(ctx, Size::fixed(100, 200)).unwrap();
+    grid.lay_out(ctx, Offset::new(10, 10), Size::fixed(100, 200))
+        .unwrap();
+
+    assert_eq!(100, grid.size().base_width().0);
+    assert_eq!(14, grid.size().base_height().0);
+
+    let offsets = grid
+        .iter()
+        .map(|child| (child.offset().x.0, child.offset().y.0))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![(10, 10), (34, 10), (56, 10), (10, 18), (34, 18), (56, 18)],
+        offsets
+    );
+}
+
+#[test]
+fn parent_and_auto_columns() {
+    let ctx = &mut 0_usize;
+
+    let mut grid = grid()
+        .columns([Track::parent(250), Track::auto(), Track::auto()])
+        .child(cell(5, 6))
+        .child(cell(12, 6))
+        .child(cell(30, 6));
+
+    grid.measure(ctx, Size::fixed(100, 200)).unwrap();
+    grid.lay_out(ctx, Offset::zero(), Size::fixed(100, 200))
+        .unwrap();
+
+    assert_eq!(67, grid.size().base_width().0);
+    assert_eq!(6, grid.size().base_height().0);
+
+    let offsets = grid
+        .iter()
+        .map(|child| child.offset().x.0)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![0, 25, 37], offsets);
+}
+
+#[test]
+fn spans_enlarge_content_tracks() {
+    let ctx = &mut 0_usize;
+
+    let mut grid = grid()
+        .columns([Track::auto(), Track::auto()])
+        .child_span(0, 0, 2, 1, cell(4, 30))
+        .child(cell(10, 5))
+        .child(cell(20, 5))
+        .child_span(2, 0, 1, 2, cell(50, 5));
+
+    grid.measure(ctx, Size::fixed(100, 200)).unwrap();
+    grid.lay_out(ctx, Offset::zero(), Size::fixed(100, 200))
+        .unwrap();
+
+    assert_eq!(50, grid.size().base_width().0);
+    assert_eq!(35, grid.size().base_height().0);
+
+    let areas = grid.areas();
+    assert_eq!((0, 1), (areas[1].row, areas[1].column));
+    assert_eq!((1, 1), (areas[2].row, areas[2].column));
+
+    let offsets = grid
+        .iter()
+        .map(|child| (child.offset().x.0, child.offset().y.0))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(0, 0), (17, 0), (17, 15), (0, 30)], offsets);
+}
+
+#[test]
+fn span_remainder_goes_to_last_track() {
+    let ctx = &mut 0_usize;
+
+    let mut grid = grid()
+        .columns([Track::auto(), Track::auto(), Track::auto()])
+        .child_span(0, 0, 1, 3, cell(10, 5));
+
+    grid.measure(ctx, Size::fixed(100, 200)).unwrap();
+    grid.lay_out(ctx, Offset::zero(), Size::fixed(100, 200))
+        .unwrap();
+
+    // shares of 3 each leave 1 to the last column
+    assert_eq!(10, grid.size().base_width().0);
+}
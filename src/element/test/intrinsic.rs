@@ -0,0 +1,144 @@
+use std::sync::{Arc, OnceLock};
+
+use crate::{
+    Dim, Error, GlyphPosition, Layout, MeasureContext, Style, StyleBuilder, TextPosition, Track,
+    columns, grid, hbox,
+    position::{Offset, Quad, Size},
+    table, text, trow,
+    unit::{Em, Unit},
+    vbox,
+};
+
+// Typesets every character as 1 em wide glyph.
+struct Glyphs;
+
+static STYLE: OnceLock<Arc<Style>> = OnceLock::new();
+
+impl MeasureContext for Glyphs {
+    fn style(&self) -> &Style {
+        STYLE.get_or_init(Style::new_default)
+    }
+
+    fn typeset(&mut self, _: &Style, text: &str) -> Result<TextPosition, Error> {
+        Ok(TextPosition {
+            width: Em(text.chars().count() as f64),
+            height: Em(1.0),
+            depth: Em(0.2),
+            positions: text
+                .chars()
+                .map(|c| GlyphPosition::new(Some(c), 1, Em(1.0), Em(0.0), Em(0.0), Em(0.0)))
+                .collect(),
+        })
+    }
+}
+
+fn boxes() -> crate::LayoutBox {
+    hbox()
+        .style(
+            StyleBuilder::new()
+                .with_horizontal_gap(2)
+                .with_padding(Quad::square(1)),
+        )
+        .child(vbox().axis_size(10).cross_size(20))
+        .child(vbox().axis_size(5).cross_size(30))
+}
+
+#[test]
+fn layout_box_max_content() {
+    let size = boxes().max_content_size(&mut Glyphs).unwrap();
+
+    assert_eq!(54, size.base_width().0);
+    assert_eq!(12, size.base_height().0);
+}
+
+#[test]
+fn layout_box_min_content() {
+    let size = boxes().min_content_size(&mut Glyphs).unwrap();
+
+    assert_eq!(32, size.base_width().0);
+    assert_eq!(17, size.base_height().0);
+}
+
+#[test]
+fn text_min_content_is_longest_word() {
+    let max = text("ab cdef").max_content_size(&mut Glyphs).unwrap();
+    let min = text("ab cdef").min_content_size(&mut Glyphs).unwrap();
+
+    assert_eq!(max.base_height(), min.base_height());
+    assert_eq!((max.base_width() * (4.0 / 7.0)).0, min.base_width().0);
+}
+
+#[test]
+fn min_content_dimension() {
+    let ctx = &mut Glyphs;
+
+    let card = hbox()
+        .axis_size(Dim::min_content())
+        .child(text("ab cdef"))
+        .child(text("ghi"));
+    let mut outer = vbox().child(card);
+
+    outer.measure(ctx, Size::fixed(1000000, 1000000)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(1000000, 1000000))
+        .unwrap();
+
+    let mut words = text("cdef");
+    let longest = words.max_content_size(ctx).unwrap().base_width();
+
+    let card = outer.iter().next().unwrap();
+    assert_eq!(longest.0, card.size().base_width().0);
+}
+
+#[test]
+fn grid_intrinsic_sizes_sum_tracks() {
+    let ctx = &mut Glyphs;
+
+    let words = || text("ab cdef");
+    let max = words().max_content_size(ctx).unwrap();
+    let min = words().min_content_size(ctx).unwrap();
+
+    let mut grid = grid()
+        .columns([Track::auto(), Track::fixed(5)])
+        .style(StyleBuilder::new().with_horizontal_gap(2))
+        .child(words())
+        .child(vbox().axis_size(4).cross_size(3))
+        .child(words());
+
+    let size = grid.max_content_size(ctx).unwrap();
+    assert_eq!((max.base_width() + Unit(7)).0, size.base_width().0);
+    assert_eq!((max.base_height() * 2.0).0, size.base_height().0);
+
+    let size = grid.min_content_size(ctx).unwrap();
+    assert_eq!((min.base_width() + Unit(7)).0, size.base_width().0);
+    assert_eq!((min.base_height() * 2.0).0, size.base_height().0);
+}
+
+#[test]
+fn table_intrinsic_sizes_are_of_its_grid() {
+    let ctx = &mut Glyphs;
+
+    let min = text("ab cdef").min_content_size(ctx).unwrap();
+
+    let mut table = table().row(trow().cell(text("ab cdef")).cell(text("ab cdef")));
+
+    let size = table.min_content_size(ctx).unwrap();
+    assert_eq!((min.base_width() * 2.0).0, size.base_width().0);
+}
+
+#[test]
+fn column_intrinsic_width_repeats_widest_child() {
+    let ctx = &mut Glyphs;
+
+    let min = text("ab cdef").min_content_size(ctx).unwrap();
+
+    let mut outer = columns()
+        .column_count(3)
+        .style(StyleBuilder::new().with_horizontal_gap(2))
+        .child(text("ab cdef"))
+        .child(text("ab"));
+
+    let size = outer.min_content_size(ctx).unwrap();
+    assert_eq!((min.base_width() * 3.0 + Unit(4)).0, size.base_width().0);
+    assert_eq!(min.base_height().0, size.base_height().0);
+}
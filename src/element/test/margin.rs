@@ -0,0 +1,96 @@
+use crate::{
+    Layout, Position, StyleBuilder, hbox,
+    position::{Offset, Quad, Size},
+    vbox,
+};
+
+#[test]
+fn margins_in_hbox() {
+    let ctx = &mut 0_usize;
+
+    let cell1 = vbox()
+        .axis_size(10)
+        .cross_size(20)
+        .style(StyleBuilder::new().with_margin(Quad::square(3)));
+    let cell2 = vbox().axis_size(10).cross_size(20);
+
+    let mut outer = hbox().child(cell1).child(cell2);
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(46, outer.size().base_width().0);
+    assert_eq!(16, outer.size().base_height().0);
+
+    let mut iter = outer.iter();
+    let cell1 = iter.next().unwrap();
+    assert_eq!(13, cell1.offset().x.0);
+    assert_eq!(13, cell1.offset().y.0);
+    assert_eq!(20, cell1.size().base_width().0);
+    assert_eq!(10, cell1.size().base_height().0);
+
+    let cell2 = iter.next().unwrap();
+    assert_eq!(36, cell2.offset().x.0);
+    assert_eq!(10, cell2.offset().y.0);
+    assert_eq!(20, cell2.size().base_width().0);
+}
+
+#[test]
+fn margins_in_vbox_add_up() {
+    let ctx = &mut 0_usize;
+
+    let cell1 = hbox()
+        .axis_size(20)
+        .cross_size(10)
+        .style(StyleBuilder::new().with_margin(Quad::h_v(0, 5)));
+    let cell2 = hbox()
+        .axis_size(20)
+        .cross_size(10)
+        .style(StyleBuilder::new().with_margin(Quad::h_v(0, 8)));
+
+    let mut outer = vbox().child(cell1).child(cell2);
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(46, outer.size().base_height().0);
+
+    let mut iter = outer.iter();
+    assert_eq!(15, iter.next().unwrap().offset().y.0);
+    assert_eq!(38, iter.next().unwrap().offset().y.0);
+}
+
+#[test]
+fn margins_in_vbox_collapse() {
+    let ctx = &mut 0_usize;
+
+    let cell1 = hbox()
+        .axis_size(20)
+        .cross_size(10)
+        .style(StyleBuilder::new().with_margin(Quad::h_v(0, 5)));
+    let cell2 = hbox()
+        .axis_size(20)
+        .cross_size(10)
+        .style(StyleBuilder::new().with_margin(Quad::h_v(0, 8)));
+
+    let mut outer = vbox()
+        .style(StyleBuilder::new().with_collapse_margins(true))
+        .child(cell1)
+        .child(cell2);
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    // the space between siblings is max(5, 8) instead of 5 + 8
+    assert_eq!(41, outer.size().base_height().0);
+
+    let mut iter = outer.iter();
+    assert_eq!(15, iter.next().unwrap().offset().y.0);
+    assert_eq!(33, iter.next().unwrap().offset().y.0);
+}
@@ -0,0 +1,51 @@
+use crate::{
+    Layout, Overflow, StyleBuilder, hbox,
+    position::{Offset, Quad, Size},
+};
+
+use super::Recorder;
+
+fn render(overflow: Overflow) -> Vec<String> {
+    let ctx = &mut Recorder::new();
+
+    let mut card = hbox()
+        .axis_size(50)
+        .cross_size(20)
+        .style(
+            StyleBuilder::new()
+                .with_overflow(overflow)
+                .with_padding(Quad::square(5)),
+        )
+        .child(hbox().axis_size(100).cross_size(10));
+
+    card.measure(ctx, Size::fixed(190, 277)).unwrap();
+    card.lay_out(ctx, Offset::new(5, 5), Size::fixed(190, 277))
+        .unwrap();
+    card.render(ctx).unwrap();
+
+    ctx.calls.clone()
+}
+
+#[test]
+fn visible_overflow_renders_as_is() {
+    assert!(render(Overflow::Visible).is_empty());
+}
+
+#[test]
+fn clipped_overflow() {
+    assert_eq!(vec!["clip 5 5 50 20", "pop clip"], render(Overflow::Clip));
+}
+
+#[test]
+fn scaled_down_overflow() {
+    // content 100 wide is scaled to 40 inside the padding, around its origin at 10, 10
+    assert_eq!(
+        vec![
+            "clip 5 5 50 20",
+            "transform 0.4 0 0 0.4 6 6",
+            "pop transform",
+            "pop clip"
+        ],
+        render(Overflow::ScaleDown)
+    );
+}
@@ -0,0 +1,364 @@
+use std::{
+    cell::Cell,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{
+    Error, FieldKind, GlyphPosition, Layout, LayoutBox, MeasureContext, NewPageOptions, Page,
+    PageFields, PageTemplate, PageTemplates, Style, StyleBuilder, Targets, TextPosition, field,
+    first_marker, hbox, last_marker, marker,
+    position::{Quad, Size},
+    reference, reference_label, section, target,
+    unit::{Em, FillPerMille, Unit},
+    vbox, vbreak_with,
+};
+
+struct Glyphs;
+
+static STYLE: OnceLock<Arc<Style>> = OnceLock::new();
+
+impl MeasureContext for Glyphs {
+    fn style(&self) -> &Style {
+        STYLE.get_or_init(Style::new_default)
+    }
+
+    fn typeset(&mut self, _: &Style, text: &str) -> Result<TextPosition, Error> {
+        Ok(TextPosition {
+            width: Em(text.chars().count() as f64),
+            height: Em(1.0),
+            depth: Em(0.2),
+            positions: text
+                .chars()
+                .map(|c| GlyphPosition::new(Some(c), 1, Em(1.0), Em(0.0), Em(0.0), Em(0.0)))
+                .collect(),
+        })
+    }
+}
+
+fn block() -> LayoutBox {
+    hbox().axis_size(40).cross_size(10)
+}
+
+fn line(kind: FieldKind, height: i64) -> LayoutBox {
+    hbox().cross_size(height).child(field(kind))
+}
+
+// Page with the area of 100 x 60 inside margins.
+fn page() -> NewPageOptions {
+    NewPageOptions::new()
+        .with_size(Size::fixed(120, 80))
+        .with_margin(Quad::square(10))
+}
+
+fn templates() -> PageTemplates {
+    let default = PageTemplate::new()
+        .header(|| line(FieldKind::SectionTitle, 10))
+        .footer(|| line(FieldKind::PageCount, 10));
+    let first = PageTemplate::new()
+        .header(|| line(FieldKind::PageNumber, 20))
+        .footer(|| line(FieldKind::PageCount, 10));
+    PageTemplates::new(page(), default).first(first)
+}
+
+// 45 blocks with sections starting before the first and the 20th of them.
+fn paginated() -> Vec<Page> {
+    let mut body = vbox().cross_size(100).child(section("Intro"));
+    for index in 0..45 {
+        if index == 19 {
+            body = body.child(section("Terms of use"));
+        }
+        body = body.child(block());
+    }
+    templates().paginate(&mut Glyphs, Box::new(body)).unwrap()
+}
+
+fn field_width(part: Option<&dyn Layout>) -> Unit {
+    part.unwrap().iter().next().unwrap().size().base_width()
+}
+
+// Count of characters of the field value, with a character as wide as the one digit page number.
+fn chars(width: Unit, digit: Unit) -> i64 {
+    (width.0 as f64 / digit.0 as f64).round() as i64
+}
+
+fn block_tops(page: &Page) -> Vec<i64> {
+    page.content()
+        .iter()
+        .filter(|child| child.size_after_wrap_ref().is_some())
+        .map(|child| child.offset().y.0)
+        .collect()
+}
+
+#[test]
+fn content_flows_between_header_and_footer() {
+    let pages = paginated();
+
+    // 3 blocks fit below the taller first header, 4 on other pages
+    assert_eq!(12, pages.len());
+    assert_eq!(vec![30, 40, 50], block_tops(&pages[0]));
+    assert_eq!(vec![20, 30, 40, 50], block_tops(&pages[1]));
+    assert_eq!(vec![20, 30], block_tops(&pages[11]));
+
+    for page in &pages {
+        let header = page.header().unwrap();
+        assert_eq!(10, header.offset().y.0);
+        let footer = page.footer().unwrap();
+        assert_eq!(60, footer.offset().y.0);
+        assert_eq!(10, footer.size().base_height().0);
+    }
+    assert_eq!(20, pages[0].header().unwrap().size().base_height().0);
+}
+
+#[test]
+fn fields_resolve_per_page() {
+    let pages = paginated();
+
+    let number = field_width(pages[0].header());
+    assert!(number > Unit(0));
+    // the page count has two digits
+    assert_eq!(2, chars(field_width(pages[0].footer()), number));
+    assert_eq!(2, chars(field_width(pages[11].footer()), number));
+
+    // the first section starting on the page, or the one continuing
+    assert_eq!(5, chars(field_width(pages[1].header()), number));
+    assert_eq!(5, chars(field_width(pages[4].header()), number));
+    assert_eq!(12, chars(field_width(pages[5].header()), number));
+    assert_eq!(12, chars(field_width(pages[6].header()), number));
+}
+
+#[test]
+fn page_fields_values() {
+    let fields = PageFields {
+        number: 3,
+        count: 12,
+        section: Some("Intro".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(Some("3".to_string()), fields.value(&FieldKind::PageNumber));
+    assert_eq!(Some("12".to_string()), fields.value(&FieldKind::PageCount));
+    assert_eq!(
+        Some("Intro".to_string()),
+        fields.value(&FieldKind::SectionTitle)
+    );
+}
+
+// Contents and references, then 45 blocks with the clause targeted before the 20th of them.
+fn contract(targets: &Targets) -> Box<dyn Layout> {
+    let references = hbox()
+        .cross_size(10)
+        .child(reference("terms"))
+        .child(reference_label("terms"));
+    let mut body = vbox()
+        .cross_size(100)
+        .child(targets.toc(0).axis_size(10))
+        .child(references);
+    for index in 0..45 {
+        if index == 19 {
+            body = body.child(target("terms").label("4.2").heading(1, "Terms"));
+        }
+        body = body.child(block());
+    }
+    Box::new(body)
+}
+
+#[test]
+fn references_resolve_to_target_pages() {
+    let passes = Cell::new(0);
+    let pages = templates()
+        .paginate_with_targets(&mut Glyphs, |targets| {
+            passes.set(passes.get() + 1);
+            contract(targets)
+        })
+        .unwrap();
+
+    // the second pass finds targets on the same pages
+    assert_eq!(2, passes.get());
+    let targets = Targets::new(&pages);
+    assert_eq!(6, targets.get("terms").unwrap().page);
+    assert_eq!(1, targets.headings().count());
+
+    let number = field_width(pages[0].header());
+    let mut content = pages[0].content().iter();
+    let toc = content.next().unwrap();
+    let line = toc.iter().next().unwrap();
+    let mut line = line.iter().skip(1);
+    assert_eq!(5, chars(line.next().unwrap().size().base_width(), number));
+    let page = line.nth(1).unwrap().size().base_width();
+    assert_eq!(number, page);
+
+    let mut references = content.next().unwrap().iter();
+    assert_eq!(number, references.next().unwrap().size().base_width());
+    let label = references.next().unwrap().size().base_width();
+    assert_eq!(3, chars(label, number));
+}
+
+#[test]
+fn targets_resolve_fields() {
+    let pages = templates()
+        .paginate(
+            &mut Glyphs,
+            Box::new(vbox().child(block()).child(target("intro")).child(block())),
+        )
+        .unwrap();
+    let targets = Targets::new(&pages);
+
+    let page = FieldKind::TargetPage("intro".to_string());
+    assert_eq!(Some("1".to_string()), targets.value(&page));
+    // the page number labels a target without a label
+    let label = FieldKind::TargetLabel("intro".to_string());
+    assert_eq!(Some("1".to_string()), targets.value(&label));
+    let missing = FieldKind::TargetPage("terms".to_string());
+    assert_eq!(None, targets.value(&missing));
+    assert_eq!(0, targets.headings().count());
+}
+
+// Entries of a dictionary, five on a page, with markers before some of them.
+fn dictionary() -> Vec<Page> {
+    let header = || {
+        hbox()
+            .cross_size(10)
+            .child(first_marker("entry"))
+            .child(last_marker("entry"))
+    };
+    let templates = PageTemplates::new(page(), PageTemplate::new().header(header));
+
+    let mut body = vbox().cross_size(100);
+    for index in 0..12 {
+        let entry = match index {
+            0 => Some("a"),
+            2 => Some("bbb"),
+            4 => Some("cc"),
+            7 => Some("dddd"),
+            _ => None,
+        };
+        if let Some(entry) = entry {
+            body = body.child(marker("entry", entry));
+        }
+        body = body.child(block());
+    }
+    templates.paginate(&mut Glyphs, Box::new(body)).unwrap()
+}
+
+fn header_chars(page: &Page, digit: Unit) -> Vec<i64> {
+    page.header()
+        .unwrap()
+        .iter()
+        .map(|field| chars(field.size().base_width(), digit))
+        .collect()
+}
+
+#[test]
+fn markers_resolve_first_and_last_on_page() {
+    let pages = dictionary();
+
+    assert_eq!(3, pages.len());
+    let digit = field_width(pages[0].header());
+    assert_eq!(vec![1, 2], header_chars(&pages[0], digit));
+    assert_eq!(vec![4, 4], header_chars(&pages[1], digit));
+    // the last marker continues on pages without markers
+    assert_eq!(vec![4, 4], header_chars(&pages[2], digit));
+}
+
+#[test]
+fn mirrored_margins_swap_on_even_pages() {
+    let page = NewPageOptions::new()
+        .with_size(Size::fixed(120, 80))
+        .with_mirrored_margin(10, 15, 10, 5);
+    let templates = PageTemplates::new(page, PageTemplate::new().header(|| hbox().cross_size(10)));
+
+    let mut body = vbox().cross_size(100);
+    for _ in 0..8 {
+        body = body.child(block());
+    }
+    let pages = templates.paginate(&mut Glyphs, Box::new(body)).unwrap();
+
+    assert_eq!(2, pages.len());
+    // the inside margin is the left one of odd pages and the right one of even pages
+    assert_eq!(15, pages[0].header().unwrap().offset().x.0);
+    assert_eq!(15, pages[0].content().offset().x.0);
+    assert_eq!(5, pages[1].header().unwrap().offset().x.0);
+    assert_eq!(5, pages[1].content().offset().x.0);
+    assert_eq!(vec![20, 30, 40, 50, 60], block_tops(&pages[0]));
+}
+
+#[test]
+fn page_break_options_set_frame_of_page() {
+    let portrait = NewPageOptions::new()
+        .with_size(Size::fixed(80, 120))
+        .with_margin(Quad::square(10));
+    let footer = || {
+        hbox()
+            .cross_size(10)
+            .style(StyleBuilder::new().with_width_parent(FillPerMille::mille()))
+    };
+    let templates = PageTemplates::new(portrait, PageTemplate::new().footer(footer));
+
+    // the landscape page keeps the margins of pages
+    let landscape = NewPageOptions::new().with_size(Size::fixed(120, 80));
+    let mut body = vbox().child(block()).child(vbreak_with(landscape));
+    for _ in 0..6 {
+        body = body.child(block());
+    }
+    let pages = templates.paginate(&mut Glyphs, Box::new(body)).unwrap();
+
+    // 5 blocks fit on the landscape page, the last one continues on a page of the pages size
+    assert_eq!(3, pages.len());
+    let heights = pages
+        .iter()
+        .map(|page| page.content().size().base_height().0)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![10, 50, 10], heights);
+    let frames = pages
+        .iter()
+        .map(|page| {
+            let footer = page.footer().unwrap();
+            (footer.size().base_width().0, footer.offset().y.0)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(60, 100), (100, 60), (60, 100)], frames);
+}
+
+// Header of two section titles, which wrap into two lines with a title of the page.
+fn titled() -> PageTemplates {
+    let header = || {
+        hbox()
+            .child(field(FieldKind::SectionTitle))
+            .child(field(FieldKind::SectionTitle))
+    };
+    PageTemplates::new(page(), PageTemplate::new().header(header))
+}
+
+fn titled_body() -> Box<dyn Layout> {
+    let mut body = vbox().cross_size(100).child(section("Intro"));
+    for _ in 0..3 {
+        body = body.child(block());
+    }
+    Box::new(body)
+}
+
+fn header_bottom(page: &Page) -> i64 {
+    let header = page.header().unwrap();
+    header.offset().y.0 + header.size().base_height().0
+}
+
+#[test]
+fn taller_header_is_clamped_to_reserved_height() {
+    let pages = titled().paginate(&mut Glyphs, titled_body()).unwrap();
+
+    let tops = block_tops(&pages[0]);
+    assert_eq!(header_bottom(&pages[0]), tops[0]);
+}
+
+#[test]
+fn taller_header_repaginates() {
+    let templates = titled();
+    let estimated = templates.paginate(&mut Glyphs, titled_body()).unwrap();
+    let pages = templates
+        .paginate_with_targets(&mut Glyphs, |_| titled_body())
+        .unwrap();
+
+    let tops = block_tops(&pages[0]);
+    assert_eq!(header_bottom(&pages[0]), tops[0]);
+    assert!(tops[0] > block_tops(&estimated[0])[0]);
+}
@@ -0,0 +1,369 @@
+use crate::{
+    BreakBetween, BreakInside, Layout, LayoutBox, NewPageOptions, Orientation, Page, PaperSize,
+    Position, StyleBuilder, footnote, hbox, paginate,
+    position::{Offset, Size},
+    table, trow, vbox, vbreak, vbreak_even, vbreak_odd, vbreak_with,
+};
+
+use super::Recorder;
+
+fn block() -> LayoutBox {
+    hbox().axis_size(40).cross_size(10)
+}
+
+fn laid_out(mut layout: LayoutBox) -> Box<dyn Layout> {
+    layout
+        .measure(&mut 0_usize, Size::fixed(100, 1000))
+        .unwrap();
+    let height = layout.size_after_wrap_ref().unwrap().base_height();
+    layout
+        .lay_out(&mut 0_usize, Offset::new(10, 20), Size::fixed(100, height))
+        .unwrap();
+    Box::new(layout)
+}
+
+fn child_tops(layout: &dyn Layout) -> Vec<i64> {
+    layout.iter().map(|child| child.offset().y.0).collect()
+}
+
+#[test]
+fn fitting_layout_is_one_page() {
+    let outer = vbox().cross_size(100).child(block()).child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+
+    assert_eq!(1, pages.len());
+    assert_eq!(1, pages[0].number());
+    assert_eq!(vec![20, 30], child_tops(pages[0].content()));
+}
+
+#[test]
+fn children_split_between_pages() {
+    let mut outer = vbox().cross_size(100);
+    for _ in 0..5 {
+        outer = outer.child(block());
+    }
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+
+    assert_eq!(3, pages.len());
+    assert_eq!(
+        vec![1, 2, 3],
+        pages.iter().map(|page| page.number()).collect::<Vec<_>>()
+    );
+    assert_eq!(vec![20, 30], child_tops(pages[0].content()));
+    assert_eq!(vec![20, 30], child_tops(pages[1].content()));
+    assert_eq!(vec![20], child_tops(pages[2].content()));
+    assert_eq!(20, pages[1].content().size().base_height().0);
+    assert_eq!(10, pages[2].content().size().base_height().0);
+}
+
+#[test]
+fn paragraph_splits_between_lines() {
+    let mut paragraph = hbox();
+    for _ in 0..6 {
+        paragraph = paragraph.child(block());
+    }
+    let outer = vbox().cross_size(100).child(block()).child(paragraph);
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+
+    assert_eq!(2, pages.len());
+    assert_eq!(vec![20, 30], child_tops(pages[0].content()));
+    assert_eq!(20, pages[0].content().size().base_height().0);
+
+    // the second page starts with the rest of the paragraph
+    let rest = pages[1].content().iter().next().unwrap();
+    assert_eq!(20, rest.offset().y.0);
+    assert_eq!(vec![20, 20, 30, 30], child_tops(rest.as_ref()));
+    assert_eq!(20, pages[1].content().size().base_height().0);
+}
+
+// Paragraph of lines having two blocks each, lines are 10 high.
+fn paragraph(lines: usize, style: StyleBuilder) -> LayoutBox {
+    let mut paragraph = hbox().style(style);
+    for _ in 0..lines * 2 {
+        paragraph = paragraph.child(block());
+    }
+    paragraph
+}
+
+fn page_heights(pages: &[Page]) -> Vec<i64> {
+    pages
+        .iter()
+        .map(|page| page.content().size().base_height().0)
+        .collect()
+}
+
+#[test]
+fn orphans_move_paragraph_to_next_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(paragraph(4, StyleBuilder::new()));
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+    assert_eq!(vec![20, 20, 10], page_heights(&pages));
+
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(paragraph(4, StyleBuilder::new().with_orphans(2)));
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+    assert_eq!(vec![10, 20, 20], page_heights(&pages));
+}
+
+#[test]
+fn widows_move_lines_to_next_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(paragraph(5, StyleBuilder::new()));
+    let pages = paginate(&mut 0_usize, laid_out(outer), 55).unwrap();
+    assert_eq!(vec![50, 10], page_heights(&pages));
+
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(paragraph(5, StyleBuilder::new().with_widows(2)));
+    let pages = paginate(&mut 0_usize, laid_out(outer), 55).unwrap();
+    assert_eq!(vec![40, 20], page_heights(&pages));
+}
+
+#[test]
+fn widows_and_orphans_inherit() {
+    let outer = vbox()
+        .cross_size(100)
+        .style(StyleBuilder::new().with_widows(2).with_orphans(2))
+        .child(block())
+        .child(paragraph(3, StyleBuilder::new()));
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+    assert_eq!(vec![10, 30], page_heights(&pages));
+}
+
+#[test]
+fn unbreakable_part_overflows() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(hbox().axis_size(40).cross_size(30))
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+
+    assert_eq!(2, pages.len());
+    assert_eq!(30, pages[0].content().size().base_height().0);
+    assert_eq!(vec![20], child_tops(pages[1].content()));
+}
+
+fn styled_block(style: StyleBuilder) -> LayoutBox {
+    block().add_style(style)
+}
+
+#[test]
+fn break_before_forces_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(styled_block(
+            StyleBuilder::new().with_break_before(BreakBetween::Page),
+        ))
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 100).unwrap();
+
+    assert_eq!(vec![10, 20], page_heights(&pages));
+}
+
+#[test]
+fn break_to_right_page_inserts_blank_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(styled_block(
+            StyleBuilder::new().with_break_after(BreakBetween::Right),
+        ))
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 100).unwrap();
+
+    assert_eq!(3, pages.len());
+    assert!(!pages[0].is_blank());
+    assert!(pages[1].is_blank());
+    assert_eq!(3, pages[2].number());
+    assert_eq!(vec![20], child_tops(pages[2].content()));
+}
+
+#[test]
+fn keep_with_next_moves_both_to_next_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(styled_block(StyleBuilder::new().with_keep_with_next(true)))
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+
+    assert_eq!(vec![10, 20], page_heights(&pages));
+}
+
+#[test]
+fn avoided_break_inside_moves_paragraph() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(paragraph(2, StyleBuilder::new()).avoid_break());
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+    assert_eq!(vec![10, 20], page_heights(&pages));
+
+    let outer = vbox().cross_size(100).child(block()).child(paragraph(
+        2,
+        StyleBuilder::new().with_break_inside(BreakInside::Avoid),
+    ));
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+    assert_eq!(vec![10, 20], page_heights(&pages));
+}
+
+#[test]
+fn avoided_break_survives_later_style() {
+    let outer = vbox().cross_size(100).child(block()).child(
+        paragraph(2, StyleBuilder::new())
+            .avoid_break()
+            .style(StyleBuilder::new().with_widows(1)),
+    );
+    let pages = paginate(&mut 0_usize, laid_out(outer), 25).unwrap();
+    assert_eq!(vec![10, 20], page_heights(&pages));
+}
+
+#[test]
+fn page_break_carries_options_to_page() {
+    let landscape = NewPageOptions::new().with_paper(PaperSize::A(4), Orientation::Landscape);
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(vbreak_with(landscape))
+        .child(block())
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 100).unwrap();
+
+    assert_eq!(vec![10, 20], page_heights(&pages));
+    assert!(pages[0].options().is_none());
+    let size = pages[1].options().unwrap().size.as_ref().unwrap();
+    assert_eq!(297_000, size.base_width().0);
+}
+
+#[test]
+fn page_break_to_odd_page_inserts_blank_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(vbreak_odd(NewPageOptions::paper(PaperSize::A(5))))
+        .child(block())
+        .child(vbreak_even(None))
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 100).unwrap();
+
+    assert_eq!(4, pages.len());
+    assert!(pages[1].is_blank());
+    // the blank page is set up like the page after it
+    assert!(pages[1].options().is_some());
+    // the break goes along with the block after it
+    let mut content = pages[2].content().iter();
+    assert!(content.next().unwrap().page_break().is_some());
+    assert_eq!(20, content.next().unwrap().offset().y.0);
+    assert!(!pages[3].is_blank());
+}
+
+#[test]
+fn rendered_page_break_skips_to_page_side() {
+    let ctx = &mut Recorder::new();
+
+    vbreak().render(ctx).unwrap();
+    assert_eq!(2, ctx.page);
+    vbreak_even(None).render(ctx).unwrap();
+    assert_eq!(4, ctx.page);
+    vbreak_odd(None).render(ctx).unwrap();
+    assert_eq!(5, ctx.page);
+}
+
+#[test]
+fn page_render_ignores_page_breaks() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(vbreak())
+        .child(block());
+    let pages = paginate(&mut 0_usize, laid_out(outer), 100).unwrap();
+    let ctx = &mut Recorder::new();
+
+    for page in &pages {
+        page.render(ctx).unwrap();
+    }
+
+    assert_eq!(2, pages.len());
+    assert_eq!(1, ctx.page);
+}
+
+fn note(blocks: usize) -> LayoutBox {
+    (0..blocks).fold(vbox().cross_size(100), |note, _| note.child(block()))
+}
+
+fn referencing(note: LayoutBox) -> LayoutBox {
+    hbox().cross_size(10).child(footnote("1", note))
+}
+
+fn footnotes_top(page: &Page) -> Option<(i64, i64)> {
+    let footnotes = page.footnotes()?;
+    Some((footnotes.offset().y.0, footnotes.size().base_height().0))
+}
+
+#[test]
+fn footnote_reduces_page_content() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(referencing(hbox().axis_size(40).cross_size(15)))
+        .child(block())
+        .child(block())
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 50).unwrap();
+
+    assert_eq!(vec![30, 20], page_heights(&pages));
+    assert_eq!(Some((55, 15)), footnotes_top(&pages[0]));
+    assert!(pages[1].footnotes().is_none());
+}
+
+#[test]
+fn footnote_in_table_cell_reduces_page_content() {
+    let table = table()
+        .row(trow().cell(referencing(hbox().axis_size(40).cross_size(15))))
+        .row(trow().cell(block()))
+        .row(trow().cell(block()));
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(table)
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 50).unwrap();
+
+    assert_eq!(vec![30, 20], page_heights(&pages));
+    assert_eq!(Some((55, 15)), footnotes_top(&pages[0]));
+    assert!(pages[1].footnotes().is_none());
+}
+
+#[test]
+fn long_footnote_continues_on_next_page() {
+    let outer = vbox()
+        .cross_size(100)
+        .child(block())
+        .child(referencing(note(6)))
+        .child(block());
+
+    let pages = paginate(&mut 0_usize, laid_out(outer), 50).unwrap();
+
+    // the reference moves on, as the footnote does not fit below the first block
+    assert_eq!(vec![10, 20, 0], page_heights(&pages));
+    assert_eq!(Some((40, 30)), footnotes_top(&pages[1]));
+    assert_eq!(Some((40, 30)), footnotes_top(&pages[2]));
+}
@@ -0,0 +1,65 @@
+use crate::{
+    Layout, Position, StackAlign, StyleBuilder, hbox,
+    position::{Offset, Quad, Size},
+    stack, vbox,
+};
+
+#[test]
+fn children_share_the_area() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = stack()
+        .style(StyleBuilder::new().with_padding(Quad::square(2)))
+        .child(hbox().axis_size(40).cross_size(20))
+        .child_aligned(
+            StackAlign::Center,
+            StackAlign::Center,
+            hbox().axis_size(10).cross_size(10),
+        )
+        .child_aligned(
+            StackAlign::End,
+            StackAlign::End,
+            hbox()
+                .axis_size(10)
+                .cross_size(5)
+                .style(StyleBuilder::new().with_margin(Quad::square(1))),
+        )
+        .child_aligned(StackAlign::Stretch, StackAlign::Start, vbox().axis_size(4));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(44, outer.size().base_width().0);
+    assert_eq!(24, outer.size().base_height().0);
+
+    let mut iter = outer.iter();
+    assert_eq!(Offset::new(12, 12), *iter.next().unwrap().offset());
+    assert_eq!(Offset::new(27, 17), *iter.next().unwrap().offset());
+    assert_eq!(Offset::new(41, 26), *iter.next().unwrap().offset());
+
+    let stretched = iter.next().unwrap();
+    assert_eq!(Offset::new(12, 12), *stretched.offset());
+    assert_eq!(40, stretched.size().base_width().0);
+    assert_eq!(4, stretched.size().base_height().0);
+}
+
+#[test]
+fn fixed_size_wins_over_children() {
+    let ctx = &mut 0_usize;
+
+    let mut outer = stack().width(30).height(30).child_aligned(
+        StackAlign::End,
+        StackAlign::Center,
+        hbox().axis_size(40).cross_size(10),
+    );
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(30, outer.size().base_width().0);
+    assert_eq!(Offset::new(-10, 10), *outer.iter().next().unwrap().offset());
+}
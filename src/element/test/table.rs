@@ -0,0 +1,212 @@
+use crate::{
+    Border, Layout, Position, Rgba, Stroke, StyleBuilder, hbox, paginate,
+    position::{Offset, Quad, Size},
+    table, tcell, trow,
+    unit::Unit,
+};
+
+use super::Recorder;
+
+fn cell(width: i64, height: i64) -> impl Layout {
+    hbox().axis_size(width).cross_size(height)
+}
+
+#[test]
+fn columns_resolve_from_content() {
+    let ctx = &mut 0_usize;
+
+    let mut table = table()
+        .row(trow().cell(cell(10, 5)).cell(cell(30, 5)))
+        .row(trow().cell(cell(20, 6)).cell(cell(5, 6)));
+
+    table.measure(ctx, Size::fixed(190, 277)).unwrap();
+    table
+        .lay_out(ctx, Offset::new(10, 10), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(50, table.size().base_width().0);
+    assert_eq!(11, table.size().base_height().0);
+
+    let offsets = table
+        .iter()
+        .map(|cell| (cell.offset().x.0, cell.offset().y.0))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(10, 10), (30, 10), (10, 15), (30, 15)], offsets);
+
+    let sizes = table
+        .iter()
+        .map(|cell| (cell.size().base_width().0, cell.size().base_height().0))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(20, 5), (30, 5), (20, 6), (30, 6)], sizes);
+}
+
+#[test]
+fn header_and_footer_are_ordered() {
+    let table = table()
+        .row(trow().cell(cell(10, 5)))
+        .footer(trow().cell(cell(10, 5)))
+        .header(trow().cell(cell(10, 5)))
+        .row(trow().cell(cell(10, 5)));
+
+    assert_eq!(1, table.header_rows());
+    assert_eq!(2, table.body_rows());
+    assert_eq!(1, table.footer_rows());
+
+    let rows = table
+        .grid()
+        .areas()
+        .iter()
+        .map(|area| area.row)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![1, 3, 0, 2], rows);
+}
+
+#[test]
+fn cells_span() {
+    let ctx = &mut 0_usize;
+
+    let mut table = table()
+        .row(
+            trow()
+                .table_cell(tcell(cell(4, 20)).rowspan(2))
+                .table_cell(tcell(cell(20, 5)).colspan(2)),
+        )
+        .row(trow().cell(cell(8, 5)).cell(cell(8, 5)))
+        .row(
+            trow().table_cell(
+                tcell(cell(10, 5))
+                    .colspan(3)
+                    .style(StyleBuilder::new().with_padding(Quad::square(1))),
+            ),
+        );
+
+    table.measure(ctx, Size::fixed(190, 277)).unwrap();
+    table
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    let areas = table
+        .grid()
+        .areas()
+        .iter()
+        .map(|area| (area.row, area.column, area.row_span, area.column_span))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (0, 0, 2, 1),
+            (0, 1, 1, 2),
+            (1, 1, 1, 1),
+            (1, 2, 1, 1),
+            (2, 0, 1, 3)
+        ],
+        areas
+    );
+
+    let columns = table
+        .grid()
+        .column_tracks()
+        .iter()
+        .map(|(_, size)| size.0)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![4, 10, 10], columns);
+
+    let rows = table
+        .grid()
+        .row_tracks()
+        .iter()
+        .map(|(offset, size)| (offset.0, size.0))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(0, 10), (10, 10), (20, 7)], rows);
+
+    let last = table.iter().last().unwrap();
+    assert_eq!(24, last.size().base_width().0);
+    let content = last.iter().next().unwrap();
+    assert_eq!(1, content.offset().x.0);
+    assert_eq!(21, content.offset().y.0);
+}
+
+#[test]
+fn cell_borders_narrow_content_in_border_box() {
+    let ctx = &mut 0_usize;
+
+    let bordered = StyleBuilder::new()
+        .with_border(Border::square(Stroke::new(Rgba::black(), Unit(2))))
+        .with_border_box(true);
+    let mut table = table().row(trow().table_cell(tcell(cell(10, 5)).style(bordered)));
+
+    table.measure(ctx, Size::fixed(190, 277)).unwrap();
+    table
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(14, table.size().base_width().0);
+    assert_eq!(9, table.size().base_height().0);
+    let content = table.iter().next().unwrap().iter().next().unwrap();
+    assert_eq!((2, 2), (content.offset().x.0, content.offset().y.0));
+}
+
+#[test]
+fn split_parts_repeat_header() {
+    let ctx = &mut 0_usize;
+
+    let mut table = table()
+        .repeated_header(|| trow().cell(cell(10, 4)))
+        .rows((0..4).map(|_| trow().cell(cell(10, 10))));
+
+    table.measure(ctx, Size::fixed(190, 277)).unwrap();
+    table
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+    assert_eq!(
+        vec![14, 24, 34],
+        table.breaks().iter().map(|at| at.0).collect::<Vec<_>>()
+    );
+
+    let pages = paginate(ctx, Box::new(table), 25).unwrap();
+
+    assert_eq!(2, pages.len());
+    for page in pages.iter() {
+        let mut heights = page
+            .content()
+            .iter()
+            .map(|cell| (cell.offset().y.0, cell.size().base_height().0))
+            .collect::<Vec<_>>();
+        heights.sort();
+        assert_eq!(vec![(0, 4), (4, 10), (14, 10)], heights);
+    }
+}
+
+#[test]
+fn borders_collapse_along_spans() {
+    let ctx = &mut Recorder::new();
+
+    let thick =
+        StyleBuilder::new().with_border(Border::square(Stroke::new(Rgba::black(), Unit(4))));
+    let mut table = table()
+        .cell_border(Border::square(Stroke::new(Rgba::black(), Unit(1))))
+        .row(
+            trow()
+                .cell(cell(10, 5))
+                .table_cell(tcell(cell(10, 10)).rowspan(2)),
+        )
+        .row(trow().table_cell(tcell(cell(10, 5)).style(thick)))
+        .row(trow().table_cell(tcell(cell(20, 5)).colspan(2)));
+
+    table.measure(ctx, Size::fixed(190, 277)).unwrap();
+    table
+        .lay_out(ctx, Offset::zero(), Size::fixed(190, 277))
+        .unwrap();
+    table.render(ctx).unwrap();
+
+    // left edge of the spanning cell collapses with each neighbour before it
+    assert!(ctx.lines.contains(&(10, 5, 10, 0, 1)));
+    assert!(ctx.lines.contains(&(10, 10, 10, 5, 4)));
+    // top edge of the cell below spans collapses with each cell above it
+    assert!(ctx.lines.contains(&(0, 10, 10, 10, 4)));
+    assert!(ctx.lines.contains(&(10, 10, 20, 10, 1)));
+    assert!(
+        !ctx.lines
+            .iter()
+            .any(|line| line.0 == 10 && line.1 == 10 && line.3 == 0)
+    );
+}
@@ -0,0 +1,70 @@
+use crate::{
+    Layout, Position, hbox,
+    position::{Affine, Offset, Size},
+    transform, vbox,
+};
+
+#[test]
+fn quarter_turn_swaps_size() {
+    let ctx = &mut 0_usize;
+
+    let rotated = transform(hbox().axis_size(40).cross_size(10)).rotate(90.0);
+    let mut outer = hbox()
+        .child(rotated)
+        .child(hbox().axis_size(5).cross_size(5));
+
+    outer.measure(ctx, Size::fixed(190, 277)).unwrap();
+    outer
+        .lay_out(ctx, Offset::new(10, 20), Size::fixed(190, 277))
+        .unwrap();
+
+    assert_eq!(15, outer.size().base_width().0);
+    assert_eq!(40, outer.size().base_height().0);
+
+    let mut iter = outer.iter();
+    let rotated = iter.next().unwrap();
+    assert_eq!(Offset::new(10, 20), *rotated.offset());
+    assert_eq!(10, rotated.size().base_width().0);
+    assert_eq!(40, rotated.size().base_height().0);
+
+    // the child stays untransformed, the corners map into the own box
+    let child = rotated.iter().next().unwrap();
+    assert_eq!(Offset::new(10, 20), *child.offset());
+    assert_eq!(40, child.size().base_width().0);
+
+    assert_eq!(Offset::new(20, 20), *iter.next().unwrap().offset());
+}
+
+#[test]
+fn resolved_transform_maps_child_into_box() {
+    let ctx = &mut 0_usize;
+
+    let mut rotated = transform(vbox().axis_size(10).cross_size(40))
+        .rotate(-90.0)
+        .translate(3, 0);
+    rotated.measure(ctx, Size::fixed(190, 277)).unwrap();
+    rotated
+        .lay_out(ctx, Offset::new(10, 20), Size::fixed(10, 40))
+        .unwrap();
+
+    assert_eq!(10, rotated.size().base_width().0);
+    assert_eq!(40, rotated.size().base_height().0);
+
+    let resolved = rotated.resolved();
+    assert_eq!(Offset::new(13, 60), resolved.apply(&Offset::new(10, 20)));
+    assert_eq!(Offset::new(23, 20), resolved.apply(&Offset::new(50, 30)));
+}
+
+#[test]
+fn affine_composes_and_inverts() {
+    let transform = Affine::scaling(2.0, 3.0).then(&Affine::translation(5, 7));
+    assert_eq!(Offset::new(25, 37), transform.apply(&Offset::new(10, 10)));
+
+    let inverse = transform.inverse().unwrap();
+    assert_eq!(Offset::new(10, 10), inverse.apply(&Offset::new(25, 37)));
+    assert!(
+        Affine::rotation(45.0)
+            .then(&Affine::rotation(45.0))
+            .is_axis_aligned()
+    );
+}
@@ -112,6 +112,39 @@ impl Layout for Text {
         Ok(())
     }
 
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.measure(ctx, Size::none())?;
+
+        let InnerText::Layout(text) = &self.text else {
+            return Ok(self.size.clone());
+        };
+        if text.positions.is_empty() || text.width.is_zero() {
+            return Ok(self.size.clone());
+        }
+
+        // the longest run of glyphs without whitespace
+        let (longest, _) = text
+            .positions
+            .iter()
+            .fold((0.0, 0.0), |(longest, run), glyph| {
+                if glyph.character.is_some_and(char::is_whitespace) {
+                    (longest, 0.0)
+                } else {
+                    let run = run + *glyph.h_advance;
+                    (f64::max(longest, run), run)
+                }
+            });
+
+        let mut size = self.size.clone();
+        size.set_base_width(self.size.base_width() * (longest / *text.width));
+        Ok(size)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        self.measure(ctx, Size::none())?;
+        Ok(self.size.clone())
+    }
+
     fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
         if let InnerText::Layout(text) = &self.text {
             if !text.positions.is_empty() {
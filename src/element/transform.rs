@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use crate::{
+    Error, Footnote, Layout, MeasureContext, Position, RenderContext, Style, Styled,
+    position::{Affine, Offset, Size},
+    unit::Unit,
+};
+
+// Applies an affine transform to the child subtree. The box bounds the transformed child,
+// so that e.g. quarter turns swap width and height. Translations only move the painting.
+pub struct Transform {
+    mark: Option<&'static str>,
+    offset: Offset,
+    size: Size,
+    style: Arc<Style>,
+    child: Box<dyn Layout>,
+    transform: Affine,
+    // transform from the child coordinates into the page, resolved in lay out
+    resolved: Affine,
+}
+
+impl Transform {
+    pub fn new(child: impl Layout + 'static) -> Self {
+        Self::new_dyn(Box::new(child))
+    }
+
+    pub fn new_dyn(child: Box<dyn Layout>) -> Self {
+        Self {
+            mark: None,
+            offset: Offset::zero(),
+            size: Size::content(),
+            style: Style::new(),
+            child,
+            transform: Affine::identity(),
+            resolved: Affine::identity(),
+        }
+    }
+
+    pub fn mark(mut self, mark: &'static str) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    pub fn rotate(mut self, degrees: f64) -> Self {
+        self.transform = self.transform.then(&Affine::rotation(degrees));
+        self
+    }
+
+    pub fn scale(mut self, x: f64, y: f64) -> Self {
+        self.transform = self.transform.then(&Affine::scaling(x, y));
+        self
+    }
+
+    pub fn translate(mut self, x: impl Into<Unit>, y: impl Into<Unit>) -> Self {
+        self.transform = self.transform.then(&Affine::translation(x, y));
+        self
+    }
+
+    pub fn transform(mut self, transform: Affine) -> Self {
+        self.transform = self.transform.then(&transform);
+        self
+    }
+
+    pub fn style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into());
+        self
+    }
+
+    pub fn add_style(mut self, style: impl Into<Arc<Style>>) -> Self {
+        self.set_style(style.into().merge(&self.style));
+        self
+    }
+
+    // Transform from the child coordinates into the page after lay out.
+    pub fn resolved(&self) -> &Affine {
+        &self.resolved
+    }
+
+    // Room of the child is the room transformed back, if boxes stay axis aligned.
+    fn child_room(&self, room: &Size) -> Size {
+        let linear = self.transform.linear();
+        match linear.inverse().filter(|_| linear.is_axis_aligned()) {
+            Some(inverse) => inverse.bounds(&Offset::zero(), room).1,
+            None => room.clone(),
+        }
+    }
+}
+
+impl Position for Transform {
+    fn element(&self) -> &str {
+        "Transform"
+    }
+
+    fn mark(&self) -> &str {
+        self.mark.unwrap_or_default()
+    }
+
+    fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
+    fn offset_mut(&mut self) -> &mut Offset {
+        &mut self.offset
+    }
+
+    fn size(&self) -> &Size {
+        &self.size
+    }
+
+    fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+}
+
+impl Styled for Transform {
+    fn style_ref(&self) -> &Style {
+        self.style.as_ref()
+    }
+
+    fn set_style(&mut self, style: Arc<Style>) {
+        self.child.set_style(self.child.style_ref().inherit(&style));
+        self.style = style;
+    }
+}
+
+impl Layout for Transform {
+    fn measure(&mut self, ctx: &mut dyn MeasureContext, room: Size) -> Result<(), Error> {
+        let child_room = self.child_room(&room);
+        self.child.measure(ctx, child_room)?;
+
+        let child_size = self
+            .child
+            .size_after_wrap_ref()
+            .cloned()
+            .unwrap_or_else(Size::zero);
+        let (_, size) = self.transform.linear().bounds(&Offset::zero(), &child_size);
+        self.size = size;
+
+        Ok(())
+    }
+
+    fn lay_out(
+        &mut self,
+        ctx: &mut dyn MeasureContext,
+        offset: Offset,
+        size: Size,
+    ) -> Result<(), Error> {
+        // the child is laid out untransformed at the same offset
+        let child_size = self
+            .child
+            .size_after_wrap_ref()
+            .cloned()
+            .unwrap_or_else(Size::zero);
+        let child_room = self.child_room(&size).definite_or(&child_size);
+        self.child.lay_out(ctx, offset.clone(), child_room)?;
+
+        let child_size = self.child.size_after_lay_out().unwrap_or_else(Size::zero);
+        let linear = self.transform.linear();
+        let (corner, size) = linear.bounds(&Offset::zero(), &child_size);
+
+        // move child offset to origin, transform and move bounds to own offset
+        let to_offset = &(&offset - &corner) + &self.transform.translated();
+        self.resolved = Affine::translation(Unit::zero() - offset.x, Unit::zero() - offset.y)
+            .then(&linear)
+            .then(&Affine::translation(to_offset.x, to_offset.y));
+
+        self.offset = offset;
+        self.size = size;
+
+        Ok(())
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        ctx.push_transform(&self.resolved);
+        self.child.render(ctx)?;
+        ctx.pop_transform();
+
+        ctx.debug_frame(self.offset(), self.size());
+
+        Ok(())
+    }
+
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        let size = self.child.min_content_size(ctx)?;
+        Ok(self.transform.linear().bounds(&Offset::zero(), &size).1)
+    }
+
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        let size = self.child.max_content_size(ctx)?;
+        Ok(self.transform.linear().bounds(&Offset::zero(), &size).1)
+    }
+
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        self.child.footnotes_mut()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
+        Box::new(std::iter::once(&self.child))
+    }
+}
@@ -91,4 +91,12 @@ impl Layout for Wrap {
         self.offset = position;
         Ok(())
     }
+
+    fn min_content_size(&mut self, _: &mut dyn MeasureContext) -> Result<Size, Error> {
+        Ok(Size::zero())
+    }
+
+    fn max_content_size(&mut self, _: &mut dyn MeasureContext) -> Result<Size, Error> {
+        Ok(Size::zero())
+    }
 }
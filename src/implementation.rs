@@ -1,12 +1,29 @@
 use crate::{
-    Axis, BlockBox, Filling, Layout, LayoutBox, PageBreak, Text, Wrap, dimension::Dim,
-    position::Offset, unit::Fill,
+    Axis, BlockBox, ColumnBox, Field, FieldKind, Filling, Footnote, GridBox, Layout, LayoutBox,
+    Marker, MarkerOnPage, NewPageOptions, PageBreak, Section, StackBox, Table, TableCell, TableRow,
+    Target, Text, Transform, Wrap, dimension::Dim, position::Offset, unit::Fill,
 };
 
 pub fn bbox(offset: Offset) -> BlockBox {
     BlockBox::new(offset)
 }
 
+pub fn columns() -> ColumnBox {
+    ColumnBox::new()
+}
+
+pub fn field(kind: FieldKind) -> Field {
+    Field::new(kind)
+}
+
+pub fn footnote(reference: impl ToString, body: impl Layout + 'static) -> Footnote {
+    Footnote::new(reference, body)
+}
+
+pub fn grid() -> GridBox {
+    GridBox::new()
+}
+
 pub fn hbox() -> LayoutBox {
     LayoutBox::new(crate::Axis::Horizontal)
 }
@@ -61,6 +78,66 @@ pub fn vbreak() -> PageBreak {
     PageBreak::new()
 }
 
+pub fn vbreak_with(options: impl Into<Option<NewPageOptions>>) -> PageBreak {
+    vbreak().options(options)
+}
+
+pub fn vbreak_odd(options: impl Into<Option<NewPageOptions>>) -> PageBreak {
+    vbreak_with(options).to_odd()
+}
+
+pub fn vbreak_even(options: impl Into<Option<NewPageOptions>>) -> PageBreak {
+    vbreak_with(options).to_even()
+}
+
+pub fn section(title: impl ToString) -> Section {
+    Section::new(title)
+}
+
+pub fn marker(name: impl ToString, text: impl ToString) -> Marker {
+    Marker::new(name, text)
+}
+
+pub fn first_marker(name: impl ToString) -> Field {
+    Field::new(FieldKind::Marker(name.to_string(), MarkerOnPage::First))
+}
+
+pub fn last_marker(name: impl ToString) -> Field {
+    Field::new(FieldKind::Marker(name.to_string(), MarkerOnPage::Last))
+}
+
+pub fn stack() -> StackBox {
+    StackBox::new()
+}
+
+pub fn table() -> Table {
+    Table::new()
+}
+
+pub fn trow() -> TableRow {
+    TableRow::new()
+}
+
+pub fn tcell(layout: impl Layout + 'static) -> TableCell {
+    TableCell::new(layout)
+}
+
 pub fn text(text: impl ToString) -> Text {
     Text::new(text)
 }
+
+pub fn transform(layout: impl Layout + 'static) -> Transform {
+    Transform::new(layout)
+}
+
+pub fn target(id: impl ToString) -> Target {
+    Target::new(id)
+}
+
+pub fn reference(id: impl ToString) -> Field {
+    Field::new(FieldKind::TargetPage(id.to_string()))
+}
+
+pub fn reference_label(id: impl ToString) -> Field {
+    Field::new(FieldKind::TargetLabel(id.to_string()))
+}
@@ -7,16 +7,23 @@ pub use axis::*;
 
 pub(crate) mod dimension;
 
+mod paper;
+
+pub use paper::*;
+
 pub mod position;
 
 pub mod unit;
 
 pub(crate) mod children;
 
-use crate::{Error, Stroke, Style, TextPosition};
+use crate::{
+    BreakBetween, Error, FieldKind, Footnote, Marker, PageBreak, Stroke, Style, Target,
+    TextPosition,
+};
 
 use self::{
-    position::{Offset, Quad, Size},
+    position::{Affine, Indent, Offset, Quad, Size},
     unit::Unit,
 };
 
@@ -80,15 +87,117 @@ pub trait Layout: Send + Position + Styled {
         Ok(())
     }
 
+    // The narrowest the layout can be, i.e. all soft wrap opportunities taken.
+    fn min_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        Ok(self.size().clone())
+    }
+
+    // The widest the layout can be, i.e. no soft wrap opportunity taken.
+    fn max_content_size(&mut self, ctx: &mut dyn MeasureContext) -> Result<Size, Error> {
+        Ok(self.size().clone())
+    }
+
+    // Lines of the layout flow around floats of its parent, indents are relative to the layout edges.
+    // Returns false, if the layout cannot break its lines, so that the parent moves it aside instead.
+    fn flow_around(&mut self, indents: Vec<Indent>) -> bool {
+        false
+    }
+
+    // Offsets from the layout top, which it can be split at after lay out, e.g. tops of its lines.
+    fn breaks(&self) -> Vec<Unit> {
+        vec![]
+    }
+
+    // Offsets from the layout top, which a page break is forced at, and the kind of the break.
+    // They are among its breaks.
+    fn forced_breaks(&self) -> Vec<(Unit, BreakBetween)> {
+        vec![]
+    }
+
+    // Splits off the part below the farthest break not beyond the offset from the layout top.
+    // The part staying needs to be measured and laid out again.
+    fn split_off(&mut self, at: Unit) -> Option<Box<dyn Layout>> {
+        None
+    }
+
+    // Title of the section starting at the layout, see Section.
+    fn section_title(&self) -> Option<&str> {
+        None
+    }
+
+    // Marker of running headers and footers the layout is, see Marker.
+    fn marker(&self) -> Option<&Marker> {
+        None
+    }
+
+    // Target of references starting at the layout, see Target.
+    fn target(&self) -> Option<&Target> {
+        None
+    }
+
+    // Page break the layout is, see PageBreak.
+    fn page_break(&self) -> Option<&PageBreak> {
+        None
+    }
+
+    // Footnotes in the layout in order of the flow, see Footnote.
+    fn footnotes_mut(&mut self) -> Vec<&mut Footnote> {
+        vec![]
+    }
+
     fn iter(&self) -> Box<dyn Iterator<Item = &Box<dyn Layout>> + '_> {
         Box::new([].iter())
     }
 }
 
+// Resolves min/max content dimensions of the layout using its intrinsic size queries.
+pub(crate) fn resolve_intrinsic(
+    layout: &mut dyn Layout,
+    ctx: &mut dyn MeasureContext,
+) -> Result<(), Error> {
+    use dimension::Dim;
+
+    for min in [true, false] {
+        let wanted = |dim: &Dim| match dim {
+            Dim::MinContent(None) => min,
+            Dim::MaxContent(None) => !min,
+            _ => false,
+        };
+        let width = wanted(&layout.size().width.base);
+        let height = wanted(&layout.size().height.base);
+        if !width && !height {
+            continue;
+        }
+
+        let intrinsic = if min {
+            layout.min_content_size(ctx)?
+        } else {
+            layout.max_content_size(ctx)?
+        };
+        let size = layout.size_mut();
+        if width {
+            let width = size.width.clamped(intrinsic.base_width());
+            size.width.base.resolve(width);
+        }
+        if height {
+            let height = size.height.clamped(intrinsic.base_height());
+            size.height.base.resolve(height);
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(unused_variables)]
 pub trait MeasureContext {
     fn style(&self) -> &Style;
 
     fn typeset(&mut self, style: &Style, text: &str) -> Result<TextPosition, Error>;
+
+    // Value of a field, if known, e.g. while laying out page headers and footers.
+    fn field(&self, kind: &FieldKind) -> Option<String> {
+        None
+    }
 }
 
 pub trait RenderContext: MeasureContext {
@@ -99,6 +208,19 @@ pub trait RenderContext: MeasureContext {
 
     fn new_page(&mut self, options: Option<NewPageOptions>);
 
+    // Number of the current page from one, if the context counts pages.
+    fn page_number(&self) -> Option<usize> {
+        None
+    }
+
+    // Transforms applied to everything rendered until popped, nested ones apply innermost first.
+    fn push_transform(&mut self, transform: &Affine);
+    fn pop_transform(&mut self);
+
+    // Rectangle, which everything rendered until popped is clipped to, nested ones intersect.
+    fn push_clip(&mut self, offset: &Offset, size: &Size);
+    fn pop_clip(&mut self);
+
     fn image(&mut self, from: &Offset, to: &Offset, image: DynamicImage);
     fn line(&mut self, from: &Offset, to: &Offset, stroke: &Stroke);
     fn text(
@@ -110,10 +232,12 @@ pub trait RenderContext: MeasureContext {
     );
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct NewPageOptions {
     pub margin: Option<Quad>,
     pub size: Option<Size>,
+    // The left margin is the inside one, the right margin the outside one, see page_margin.
+    pub mirror_margin: bool,
 }
 
 impl NewPageOptions {
@@ -121,6 +245,47 @@ impl NewPageOptions {
         Default::default()
     }
 
+    pub fn paper(paper: PaperSize) -> Self {
+        Self::new().with_paper(paper, Orientation::Portrait)
+    }
+
+    pub fn paper_landscape(paper: PaperSize) -> Self {
+        Self::new().with_paper(paper, Orientation::Landscape)
+    }
+
+    pub fn with_paper(self, paper: PaperSize, orientation: Orientation) -> Self {
+        self.with_size(paper.size(orientation))
+    }
+
+    // Margins of bound documents, the inside margin is at the binding.
+    pub fn with_mirrored_margin(
+        mut self,
+        top: impl Into<Unit>,
+        inside: impl Into<Unit>,
+        bottom: impl Into<Unit>,
+        outside: impl Into<Unit>,
+    ) -> Self {
+        let margin = Quad::empty()
+            .with_top(top)
+            .with_left(inside)
+            .with_bottom(bottom)
+            .with_right(outside);
+        self.margin = Some(margin);
+        self.mirror_margin = true;
+        self
+    }
+
+    // Margin of the page numbered from one, odd pages are right ones, so that their inside
+    // margin is the left one.
+    pub fn page_margin(&self, number: usize) -> Option<Quad> {
+        let margin = self.margin.as_ref()?;
+        Some(if self.mirror_margin && number.is_multiple_of(2) {
+            margin.mirrored()
+        } else {
+            margin.clone()
+        })
+    }
+
     pub fn with_margin(mut self, margin: Quad) -> Self {
         self.margin = Some(margin);
         self
@@ -1,4 +1,8 @@
-use crate::{Axis, Layout, unit::Unit};
+use crate::{
+    AlignItems, Axis, Layout,
+    position::{Indent, Quad},
+    unit::Unit,
+};
 
 use super::position::{Offset, Size};
 
@@ -9,16 +13,26 @@ pub struct Line<'a> {
     offset: Unit,
     size: Size,
     content: Vec<&'a mut Box<dyn Layout>>,
+    margins: Vec<Quad>,
+    indent: (Unit, Unit),
 }
 
 #[allow(clippy::borrowed_box)]
 
 impl<'a> Line<'a> {
-    fn new(offset: Unit, size: Size, content: Vec<&'a mut Box<dyn Layout>>) -> Self {
+    fn new(
+        offset: Unit,
+        size: Size,
+        content: Vec<&'a mut Box<dyn Layout>>,
+        margins: Vec<Quad>,
+        indent: (Unit, Unit),
+    ) -> Self {
         Self {
             offset,
             size,
             content,
+            margins,
+            indent,
         }
     }
 
@@ -31,6 +45,11 @@ impl<'a> Line<'a> {
         &self.size
     }
 
+    // Room taken from the line start and end by floats of the parent.
+    pub fn indent(&self) -> (Unit, Unit) {
+        self.indent
+    }
+
     #[cfg(test)]
     pub fn len(&self) -> usize {
         self.content.len()
@@ -41,11 +60,48 @@ impl<'a> Line<'a> {
         &self.content
     }
 
-    pub fn content_mut(&'a mut self) -> &'a mut [&'a mut Box<dyn Layout>] {
-        &mut self.content
+    pub fn children(&self) -> impl Iterator<Item = (&Box<dyn Layout>, &Quad)> {
+        self.content
+            .iter()
+            .map(|child| &**child)
+            .zip(self.margins.iter())
     }
+
+    // Children with their margins, already collapsed if the parent asked for it.
+    pub fn children_mut(&mut self) -> impl Iterator<Item = (&mut Box<dyn Layout>, &Quad)> {
+        self.content
+            .iter_mut()
+            .map(|child| &mut **child)
+            .zip(self.margins.iter())
+    }
+}
+
+// Margins of children, adjacent vertical margins of siblings in vertical axis may collapse as in CSS,
+// i.e. the space between siblings is the bigger of both margins instead of their sum.
+pub fn effective_margins(axis: Axis, children: &[Box<dyn Layout>], collapse: bool) -> Vec<Quad> {
+    let collapse = collapse && matches!(axis, Axis::Vertical);
+    let mut previous_bottom = None;
+
+    children
+        .iter()
+        .map(|child| {
+            let margin = child.style_ref().margin().clone();
+            if !collapse || child.size_after_wrap_ref().is_none() {
+                return margin;
+            }
+
+            match previous_bottom.replace(margin.bottom_size()) {
+                Some(previous_bottom) => {
+                    let top = margin.top_size();
+                    margin.with_top((top - previous_bottom).max(Unit::zero()))
+                }
+                None => margin,
+            }
+        })
+        .collect()
 }
 
+#[cfg(test)]
 pub fn lay_out_native<'a>(
     axis: Axis,
     children: &'a mut [Box<dyn Layout>],
@@ -54,6 +110,36 @@ pub fn lay_out_native<'a>(
     cross_gap: impl Into<Unit>,
     wrap: bool,
     respect_baseline: bool,
+) -> Vec<Line<'a>> {
+    let margins = effective_margins(axis, children, false);
+    lay_out_native_with_margins(
+        axis,
+        children,
+        margins,
+        axis_room,
+        axis_gap,
+        cross_gap,
+        wrap,
+        if respect_baseline {
+            AlignItems::Baseline
+        } else {
+            AlignItems::Start
+        },
+        &[],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn lay_out_native_with_margins<'a>(
+    axis: Axis,
+    children: &'a mut [Box<dyn Layout>],
+    margins: Vec<Quad>,
+    axis_room: impl Into<Unit>,
+    axis_gap: impl Into<Unit>,
+    cross_gap: impl Into<Unit>,
+    wrap: bool,
+    align_items: AlignItems,
+    indents: &[Indent],
 ) -> Vec<Line<'a>> {
     if children.is_empty() {
         return vec![];
@@ -65,48 +151,90 @@ pub fn lay_out_native<'a>(
     let mut lines = vec![];
     let mut remaining = children.len();
 
-    let mut offset = Offset::zero();
+    // lines flowing around floats start and end indented
+    let cross = axis.cross();
+    let mut indent = Indent::at(indents, Unit::zero());
+    let mut offset = axis.advance_dim(&Offset::zero(), indent.0);
     let mut line_size = Size::zero();
     let mut line = Vec::with_capacity(remaining - 1);
+    let mut line_margins = Vec::with_capacity(remaining - 1);
+    let mut line_in_flow = false;
+
+    for (child, margin) in children.iter_mut().zip(margins) {
+        // floats in vertical axis are out of flow, the parent places them
+        if matches!(axis, Axis::Vertical) && child.style_ref().float().is_some() {
+            *child.offset_mut() = margin.offset(&offset);
+            line.push(child);
+            line_margins.push(margin);
+            continue;
+        }
 
-    for child in children {
-        let line_gap = if line.is_empty() {
+        let line_gap = if !line_in_flow {
             Unit::zero()
         } else {
             axis_gap
         };
 
-        if wrap && axis.base_size(&line_size) + line_gap + axis.base_size(child.size()) > wrap_size
-        {
-            let next_line_offset = axis
-                .cross()
-                .advance_dim(&offset, axis.cross().base_size(&line_size) + cross_gap);
+        // children occupy their size including margins, aligning on the chosen baseline
+        let size_after_wrap = child.size_after_wrap_ref().cloned().map(|mut size| {
+            margin.widen(None, Some(&mut size));
+            match align_items {
+                AlignItems::LastBaseline => size.last_baseline(),
+                _ => size,
+            }
+        });
+        let child_axis_size = size_after_wrap
+            .as_ref()
+            .map(|size| axis.base_size(size))
+            .unwrap_or_else(|| axis.base_size(child.size()));
+
+        let line_room = wrap_size - indent.0 - indent.1;
+        if wrap && axis.base_size(&line_size) + line_gap + child_axis_size > line_room {
+            let next_line_offset =
+                cross.advance_dim(&offset, cross.base_size(&line_size) + cross_gap);
 
             remaining -= line.len();
-            lines.push(Line::new(axis.cross().offset(&offset), line_size, line));
+            lines.push(Line::new(
+                cross.offset(&offset),
+                line_size,
+                line,
+                line_margins,
+                indent,
+            ));
 
             offset = next_line_offset;
-            axis.set_offset(&mut offset, Unit::zero());
+            indent = Indent::at(indents, cross.offset(&offset));
+            axis.set_offset(&mut offset, indent.0);
 
             line_size = Size::zero();
             line = Vec::with_capacity(remaining);
+            line_margins = Vec::with_capacity(remaining);
+            line_in_flow = false;
         } else if line_gap > Unit::zero() {
             offset = axis.advance_dim(&offset, line_gap);
             line_size = axis.extend_dim(&line_size, line_gap);
         }
 
-        if let Some(size_after_wrap) = child.size_after_wrap_ref().cloned() {
-            line_size = axis.extend_size(&line_size, &size_after_wrap, respect_baseline);
+        if let Some(size_after_wrap) = size_after_wrap {
+            line_size = axis.extend_size(&line_size, &size_after_wrap, align_items.is_baseline());
 
-            *child.offset_mut() = offset.clone();
+            *child.offset_mut() = margin.offset(&offset);
             offset = axis.advance_dim(&offset, axis.base_size(&size_after_wrap));
 
             line.push(child);
+            line_margins.push(margin);
+            line_in_flow = true;
         }
     }
 
     if !line.is_empty() {
-        lines.push(Line::new(axis.cross().offset(&offset), line_size, line));
+        lines.push(Line::new(
+            cross.offset(&offset),
+            line_size,
+            line,
+            line_margins,
+            indent,
+        ));
     }
 
     lines
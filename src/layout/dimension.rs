@@ -8,11 +8,58 @@ use rtext::Apply;
 
 use crate::unit::{Fill, FillPerMille, Unit, add_fill, sub_fill};
 
+// Linear combination of parent fraction, fixed units and font size, e.g. half of parent minus 5 mm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calc {
+    parent: FillPerMille,
+    fixed: Unit,
+    // per mille of font size
+    em: i64,
+}
+
+impl Calc {
+    pub const fn new() -> Self {
+        Self {
+            parent: FillPerMille::none(),
+            fixed: Unit(0),
+            em: 0,
+        }
+    }
+
+    pub fn parent(mut self, fill: impl Into<FillPerMille>) -> Self {
+        self.parent = fill.into();
+        self
+    }
+
+    pub fn fixed(mut self, unit: impl Into<Unit>) -> Self {
+        self.fixed = unit.into();
+        self
+    }
+
+    pub fn em_per_mille(mut self, em: i64) -> Self {
+        self.em = em;
+        self
+    }
+
+    pub fn resolve(&self, parent: Unit, font_size: Unit) -> Unit {
+        parent * (self.parent, FillPerMille::mille())
+            + self.fixed
+            + Unit(font_size.0 * self.em / 1000)
+    }
+}
+
+impl Default for Calc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MaybeDim {
     None,
     Fixed(Unit),
     Parent(FillPerMille, Option<Unit>),
+    Calc(Calc, Option<Unit>),
 }
 
 impl MaybeDim {
@@ -20,12 +67,20 @@ impl MaybeDim {
         match self {
             Self::None => None,
             Self::Fixed(unit) => Some(unit),
-            Self::Parent(_, unit) => unit.as_mut(),
+            Self::Parent(_, unit) | Self::Calc(_, unit) => unit.as_mut(),
         }
     }
 
     pub const fn is_parented(&self) -> bool {
-        matches!(self, Self::Parent(..))
+        matches!(self, Self::Parent(..) | Self::Calc(..))
+    }
+
+    pub fn parented_size(&self, parent: Unit, font_size: Unit) -> Option<Unit> {
+        match self {
+            Self::Parent(fill, _) => Some(parent * (*fill, FillPerMille::mille())),
+            Self::Calc(calc, _) => Some(calc.resolve(parent, font_size)),
+            _ => None,
+        }
     }
 
     pub const fn parent_fill(&self) -> FillPerMille {
@@ -39,7 +94,7 @@ impl MaybeDim {
         match self {
             Self::None => None,
             Self::Fixed(size) => Some(*size),
-            Self::Parent(_, size) => *size,
+            Self::Parent(_, size) | Self::Calc(_, size) => *size,
         }
     }
 
@@ -48,8 +103,10 @@ impl MaybeDim {
     }
 
     pub fn resolve(&mut self, size: Unit) {
-        if let Self::Parent(fill, None) = self {
-            *self = Self::Parent(*fill, Some(size));
+        match self {
+            Self::Parent(fill, None) => *self = Self::Parent(*fill, Some(size)),
+            Self::Calc(calc, None) => *self = Self::Calc(*calc, Some(size)),
+            _ => (),
         }
     }
 
@@ -76,6 +133,12 @@ impl From<FillPerMille> for MaybeDim {
     }
 }
 
+impl From<Calc> for MaybeDim {
+    fn from(calc: Calc) -> Self {
+        Self::Calc(calc, None)
+    }
+}
+
 impl Add<&MaybeDim> for &MaybeDim {
     type Output = MaybeDim;
 
@@ -107,6 +170,9 @@ pub enum Dim {
     Content(Option<Unit>),
     Fixed(Unit),
     Parent(FillPerMille, Option<Unit>),
+    Calc(Calc, Option<Unit>),
+    MinContent(Option<Unit>),
+    MaxContent(Option<Unit>),
 }
 
 impl Dim {
@@ -114,6 +180,18 @@ impl Dim {
         Self::Content(None)
     }
 
+    pub const fn min_content() -> Self {
+        Self::MinContent(None)
+    }
+
+    pub const fn max_content() -> Self {
+        Self::MaxContent(None)
+    }
+
+    pub const fn is_intrinsic(&self) -> bool {
+        matches!(self, Self::MinContent(_) | Self::MaxContent(_))
+    }
+
     pub const fn is_fixed(&self) -> bool {
         matches!(self, Self::Fixed(_))
     }
@@ -123,16 +201,29 @@ impl Dim {
     }
 
     pub const fn is_parented(&self) -> bool {
-        matches!(self, Self::Parent(..))
+        matches!(self, Self::Parent(..) | Self::Calc(..))
     }
 
     pub const fn is_resolved(&self) -> bool {
         matches!(
             self,
-            Self::Content(Some(_)) | Self::Fixed(_) | Self::Parent(_, Some(_))
+            Self::Content(Some(_))
+                | Self::Fixed(_)
+                | Self::Parent(_, Some(_))
+                | Self::Calc(_, Some(_))
+                | Self::MinContent(Some(_))
+                | Self::MaxContent(Some(_))
         )
     }
 
+    pub fn parented_size(&self, parent: Unit, font_size: Unit) -> Option<Unit> {
+        match self {
+            Self::Parent(fill, _) => Some(parent * (*fill, FillPerMille::mille())),
+            Self::Calc(calc, _) => Some(calc.resolve(parent, font_size)),
+            _ => None,
+        }
+    }
+
     pub const fn parent_fill(&self) -> FillPerMille {
         match self {
             Self::Parent(fill, _) => *fill,
@@ -142,9 +233,9 @@ impl Dim {
 
     pub const fn size(&self) -> Option<Unit> {
         match self {
-            Self::Content(size) => *size,
+            Self::Content(size) | Self::MinContent(size) | Self::MaxContent(size) => *size,
             Self::Fixed(size) => Some(*size),
-            Self::Parent(_, size) => *size,
+            Self::Parent(_, size) | Self::Calc(_, size) => *size,
         }
     }
 
@@ -152,7 +243,10 @@ impl Dim {
         let size = size.into();
         match self {
             Self::Parent(fill, _) => *self = Self::Parent(*fill, Some(size)),
+            Self::Calc(calc, _) => *self = Self::Calc(*calc, Some(size)),
             Self::Content(_) => *self = Self::Content(Some(size)),
+            Self::MinContent(_) => *self = Self::MinContent(Some(size)),
+            Self::MaxContent(_) => *self = Self::MaxContent(Some(size)),
             Self::Fixed(_) => *self = Self::Fixed(size),
         }
     }
@@ -161,7 +255,10 @@ impl Dim {
         let size = size.into();
         match self {
             Self::Parent(fill, None) => *self = Self::Parent(*fill, Some(size)),
+            Self::Calc(calc, None) => *self = Self::Calc(*calc, Some(size)),
             Self::Content(None) => *self = Self::Content(Some(size)),
+            Self::MinContent(None) => *self = Self::MinContent(Some(size)),
+            Self::MaxContent(None) => *self = Self::MaxContent(Some(size)),
             _ => (),
         }
     }
@@ -225,12 +322,19 @@ impl From<FillPerMille> for Dim {
     }
 }
 
+impl From<Calc> for Dim {
+    fn from(calc: Calc) -> Self {
+        Self::Calc(calc, None)
+    }
+}
+
 impl From<MaybeDim> for Dim {
     fn from(dim: MaybeDim) -> Self {
         match dim {
             MaybeDim::None => Self::Content(None),
             MaybeDim::Fixed(unit) => Self::Fixed(unit),
             MaybeDim::Parent(fill, unit) => Self::Parent(fill, unit),
+            MaybeDim::Calc(calc, unit) => Self::Calc(calc, unit),
         }
     }
 }
@@ -428,11 +532,11 @@ impl FlexDim {
         }
     }
 
-    pub fn resolve_parented(&mut self, parent_size: impl Into<Unit>) {
+    // Resolves parent fractions and calc expressions, whose em parts refer to the font size.
+    pub fn resolve_parented(&mut self, parent_size: impl Into<Unit>, font_size: Unit) {
         let parent = parent_size.into();
 
-        if self.min.is_parented() {
-            let min = parent * (self.min.parent_fill(), FillPerMille::mille());
+        if let Some(min) = self.min.parented_size(parent, font_size) {
             if matches!(self.max.size(), Some(max) if max < min) {
                 self.max.resolve(min);
             }
@@ -442,8 +546,7 @@ impl FlexDim {
             self.min.resolve(min);
         }
 
-        if self.max.is_parented() {
-            let max = parent * (self.max.parent_fill(), FillPerMille::mille());
+        if let Some(max) = self.max.parented_size(parent, font_size) {
             if matches!(self.min.size(), Some(min) if min > max) {
                 self.min.resolve(max);
             }
@@ -453,8 +556,7 @@ impl FlexDim {
             self.max.resolve(max);
         }
 
-        if self.base.is_parented() {
-            let base = parent * (self.base.parent_fill(), FillPerMille::mille());
+        if let Some(base) = self.base.parented_size(parent, font_size) {
             match self.min.size() {
                 Some(min) if min > base => self.base.resolve(min),
                 _ => (),
@@ -488,6 +590,11 @@ impl FlexDim {
         }
     }
 
+    pub fn clamped(&self, size: Unit) -> Unit {
+        size.apply_then(self.max.size(), |size, max| size.min(max))
+            .apply_then(self.min.size(), |size, min| size.max(min))
+    }
+
     pub fn size_filled(&self, room: Unit) -> Unit {
         let size = self.base_size();
         match size.cmp(&room) {
@@ -542,6 +649,73 @@ impl FlexDim {
     }
 }
 
+// Resolves sizes of items sharing free space as in CSS flexbox. Items clamped by their min/max get frozen
+// and the space they did not take is redistributed among the others, until no item violates its limits.
+// Shrinking is weighted by the base size, so that small items do not reach zero before big ones.
+pub fn distribute(dims: &[FlexDim], free: Unit) -> Vec<Unit> {
+    let bases = dims.iter().map(FlexDim::base_size).collect::<Vec<_>>();
+    let mut sizes = bases.clone();
+    if free == Unit::zero() {
+        return sizes;
+    }
+
+    let growing = free > Unit::zero();
+    let weight = |index: usize| -> i128 {
+        let dim = &dims[index];
+        if growing {
+            dim.grow.map_or(0, |grow| grow.0 as i128)
+        } else {
+            dim.shrink
+                .map_or(0, |shrink| shrink.0 as i128 * bases[index].0 as i128)
+        }
+    };
+
+    let mut frozen = (0..dims.len())
+        .map(|index| weight(index) == 0)
+        .collect::<Vec<_>>();
+    let total = bases.iter().fold(free, |sum, base| sum + *base);
+    let mut targets = bases.clone();
+
+    while frozen.iter().any(|frozen| !frozen) {
+        let used = (0..dims.len()).fold(Unit::zero(), |sum, index| {
+            sum + if frozen[index] {
+                sizes[index]
+            } else {
+                bases[index]
+            }
+        });
+        let remaining = total - used;
+        let sum_weight = (0..dims.len())
+            .filter(|index| !frozen[*index])
+            .map(weight)
+            .sum::<i128>();
+
+        let mut violation = Unit::zero();
+        for index in (0..dims.len()).filter(|index| !frozen[*index]) {
+            // rounded the same way as Unit * (Fill, Fill)
+            let rounding = remaining.0.signum() as i128 * sum_weight / 2;
+            let share = (remaining.0 as i128 * weight(index) + rounding) / sum_weight;
+            targets[index] = bases[index] + Unit(share as i64);
+            sizes[index] = dims[index].clamped(targets[index]).max(Unit::zero());
+            violation += sizes[index] - targets[index];
+        }
+
+        for (index, frozen) in frozen
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, frozen)| !**frozen)
+        {
+            *frozen = match violation.cmp(&Unit::zero()) {
+                Ordering::Equal => true,
+                Ordering::Greater => sizes[index] > targets[index],
+                Ordering::Less => sizes[index] < targets[index],
+            };
+        }
+    }
+
+    sizes
+}
+
 impl From<Unit> for FlexDim {
     fn from(size: Unit) -> Self {
         Self {
@@ -706,7 +880,7 @@ impl DivAssign<f64> for FlexDim {
 #[cfg(test)]
 mod tests {
     use crate::{
-        dimension::Dim,
+        dimension::{Calc, Dim},
         layout::dimension::FlexDim,
         unit::{Fill, Unit},
     };
@@ -962,4 +1136,24 @@ mod tests {
         let size = dim.size_distributed((-10).into(), Some(10.into()), Some(10.into()));
         assert_eq!(Unit::from(5), size);
     }
+
+    #[test]
+    fn it_resolves_calc() {
+        let calc = Calc::new().parent(500).fixed(-5);
+        let mut dim = FlexDim::from(Unit::zero()).with_min(calc).with_max(calc);
+        dim.base = calc.into();
+
+        dim.resolve_parented(190, Unit::zero());
+
+        assert_eq!(Some(Unit::from(90)), dim.base.size());
+        assert_eq!(Some(Unit::from(90)), dim.min.size());
+        assert_eq!(Some(Unit::from(90)), dim.max.size());
+
+        let mut dim = FlexDim::from(Unit::zero());
+        dim.base = Calc::new().fixed(2).em_per_mille(1500).into();
+
+        dim.resolve_parented(190, Unit::from(10));
+
+        assert_eq!(Some(Unit::from(17)), dim.base.size());
+    }
 }
@@ -0,0 +1,115 @@
+use crate::unit::{Mm, Unit};
+
+use super::position::Size;
+
+// Paper sizes of the ISO 216 A and B series and ISO 269 C series for envelopes, numbered 0 to
+// 10, North American sizes and common envelopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperSize {
+    A(u8),
+    B(u8),
+    C(u8),
+    Letter,
+    Legal,
+    Tabloid,
+    // 110 × 220 mm
+    EnvelopeDl,
+    // 4 1/8 × 9 1/2 in
+    Envelope10,
+    // 3 7/8 × 7 1/2 in
+    EnvelopeMonarch,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+impl PaperSize {
+    // Width and height in portrait orientation.
+    pub fn dimensions(&self) -> (Unit, Unit) {
+        let (width, height) = match self {
+            PaperSize::A(number) => iso(841.0, 1189.0, *number),
+            PaperSize::B(number) => iso(1000.0, 1414.0, *number),
+            PaperSize::C(number) => iso(917.0, 1297.0, *number),
+            PaperSize::Letter => (inches(8.5), inches(11.0)),
+            PaperSize::Legal => (inches(8.5), inches(14.0)),
+            PaperSize::Tabloid => (inches(11.0), inches(17.0)),
+            PaperSize::EnvelopeDl => (110.0, 220.0),
+            PaperSize::Envelope10 => (inches(4.125), inches(9.5)),
+            PaperSize::EnvelopeMonarch => (inches(3.875), inches(7.5)),
+        };
+        (Mm(width).into(), Mm(height).into())
+    }
+
+    pub fn size(&self, orientation: Orientation) -> Size {
+        let (width, height) = self.dimensions();
+        match orientation {
+            Orientation::Portrait => Size::fixed(width, height),
+            Orientation::Landscape => Size::fixed(height, width),
+        }
+    }
+}
+
+// Every next size halves the longer side of the previous one, rounded down to millimetres.
+fn iso(width: f64, height: f64, number: u8) -> (f64, f64) {
+    (0..number).fold((width, height), |(width, height), _| {
+        ((height / 2.0).floor(), width)
+    })
+}
+
+fn inches(inches: f64) -> f64 {
+    inches * 25.4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Orientation, PaperSize};
+    use crate::NewPageOptions;
+
+    fn mm(paper: PaperSize) -> (i64, i64) {
+        let (width, height) = paper.dimensions();
+        (width.0, height.0)
+    }
+
+    #[test]
+    fn iso_series_halve() {
+        assert_eq!((841_000, 1_189_000), mm(PaperSize::A(0)));
+        assert_eq!((210_000, 297_000), mm(PaperSize::A(4)));
+        assert_eq!((148_000, 210_000), mm(PaperSize::A(5)));
+        assert_eq!((26_000, 37_000), mm(PaperSize::A(10)));
+        assert_eq!((250_000, 353_000), mm(PaperSize::B(4)));
+        assert_eq!((162_000, 229_000), mm(PaperSize::C(5)));
+        assert_eq!((28_000, 40_000), mm(PaperSize::C(10)));
+    }
+
+    #[test]
+    fn us_sizes_in_inches() {
+        assert_eq!((215_900, 279_400), mm(PaperSize::Letter));
+        assert_eq!((215_900, 355_600), mm(PaperSize::Legal));
+        assert_eq!((279_400, 431_800), mm(PaperSize::Tabloid));
+        assert_eq!((104_775, 241_300), mm(PaperSize::Envelope10));
+        assert_eq!((110_000, 220_000), mm(PaperSize::EnvelopeDl));
+    }
+
+    #[test]
+    fn landscape_swaps_sides() {
+        let size = PaperSize::A(4).size(Orientation::Landscape);
+        assert_eq!(297_000, size.base_width().0);
+        assert_eq!(210_000, size.base_height().0);
+    }
+
+    #[test]
+    fn mirrored_margins_swap_on_even_pages() {
+        let options = NewPageOptions::paper(PaperSize::A(5)).with_mirrored_margin(10, 25, 15, 20);
+        assert_eq!(148_000, options.size.as_ref().unwrap().base_width().0);
+
+        let odd = options.page_margin(3).unwrap();
+        assert_eq!((25, 20), (odd.left_size().0, odd.right_size().0));
+        let even = options.page_margin(2).unwrap();
+        assert_eq!((20, 25), (even.left_size().0, even.right_size().0));
+        assert_eq!((10, 15), (even.top_size().0, even.bottom_size().0));
+    }
+}
@@ -5,8 +5,8 @@ use std::{
 
 use crate::{
     Axis, Style,
-    dimension::Dim,
-    unit::{Unit, sub_unit},
+    dimension::{Calc, Dim},
+    unit::{Fill, Unit, sub_unit},
 };
 
 use super::dimension::FlexDim;
@@ -104,6 +104,8 @@ pub struct Size {
     pub width: FlexDim,
     pub height: FlexDim,
     depth: Option<Unit>,
+    // depth below the last baseline, if it differs from the first one
+    last_depth: Option<Unit>,
 }
 
 impl Debug for Size {
@@ -112,6 +114,7 @@ impl Debug for Size {
             .field("w", &self.width)
             .field("h", &self.height)
             .field("d", &format!("{:?}", self.depth))
+            .field("ld", &format!("{:?}", self.last_depth))
             .finish()
     }
 }
@@ -121,6 +124,7 @@ impl Size {
         width: FlexDim::none(),
         height: FlexDim::none(),
         depth: None,
+        last_depth: None,
     };
 
     pub fn none() -> Self {
@@ -132,6 +136,7 @@ impl Size {
             width: FlexDim::content(),
             height: FlexDim::content(),
             depth: None,
+            last_depth: None,
         }
     }
 
@@ -140,6 +145,7 @@ impl Size {
             width: FlexDim::fixed(width),
             height: FlexDim::fixed(height),
             depth: None,
+            last_depth: None,
         }
     }
 
@@ -152,6 +158,7 @@ impl Size {
             width: FlexDim::fixed(width),
             height: FlexDim::fixed(height),
             depth: Some(depth.into()),
+            last_depth: None,
         }
     }
 
@@ -161,9 +168,67 @@ impl Size {
             width: Unit::zero().into(),
             height: Unit::zero().into(),
             depth: None,
+            last_depth: None,
         }
     }
 
+    // Derives one dimension from the other, so that they keep the aspect ratio of the style.
+    // The width drives the height, unless only the height is definite. Stretches of the driving
+    // dimension are resolved against the room, if known, and min/max of the derived dimension feed back.
+    pub fn apply_aspect_ratio(&mut self, style: &Style, room: Option<&Size>) {
+        let Some((width_part, height_part)) = style.aspect_ratio() else {
+            return;
+        };
+        if !self.width.is_content() && !self.height.is_content() {
+            return;
+        }
+
+        let height_drives = self.width.is_content() && !self.height.is_content();
+        let (driver, derived, driver_room, ratio): (_, _, _, (Fill, Fill)) = if height_drives {
+            (
+                &mut self.height,
+                &mut self.width,
+                room.map(|room| room.base_height()),
+                (width_part, height_part),
+            )
+        } else {
+            (
+                &mut self.width,
+                &mut self.height,
+                room.map(|room| room.base_width()),
+                (height_part, width_part),
+            )
+        };
+
+        if let Some(driver_room) = driver_room.filter(|_| driver.is_dyn()) {
+            let size = driver.size_filled(driver_room);
+            driver.base.set_size(size);
+        }
+        let Some(driver_size) = driver.base.size() else {
+            return;
+        };
+
+        let ideal = driver_size * ratio;
+        let size = derived.clamped(ideal);
+        derived.base.set_size(size);
+        if size != ideal {
+            let size = driver.clamped(size * (ratio.1, ratio.0));
+            driver.base.set_size(size);
+        }
+    }
+
+    // Own definite dimensions, otherwise the intrinsic size clamped by own min/max.
+    pub fn definite_or(&self, intrinsic: &Size) -> Size {
+        let definite = |own: &FlexDim, intrinsic: Unit| match own.base.size() {
+            Some(size) if !own.is_content() => size,
+            _ => own.clamped(intrinsic),
+        };
+        Size::fixed(
+            definite(&self.width, intrinsic.base_width()),
+            definite(&self.height, intrinsic.base_height()),
+        )
+    }
+
     pub fn apply_style(&mut self, axis: Axis, style: &Style) {
         self.width.complete_with_style(
             style.width(),
@@ -230,12 +295,41 @@ impl Size {
         sub_unit(self.height.base.size(), self.depth).unwrap_or_default()
     }
 
+    pub fn last_depth(&self) -> Option<Unit> {
+        self.last_depth.or(self.depth)
+    }
+
+    pub fn set_last_depth(&mut self, depth: Option<impl Into<Unit>>) {
+        self.last_depth = depth.map(|depth| depth.into());
+        self.drop_equal_last_depth();
+    }
+
+    pub fn last_ascent(&self) -> Option<Unit> {
+        self.last_depth()
+            .and_then(|depth| self.height.base.size().map(|height| height - depth))
+    }
+
+    // The same size aligning on its last baseline instead of the first one.
+    pub fn last_baseline(&self) -> Size {
+        let mut size = self.clone();
+        size.depth = self.last_depth();
+        size
+    }
+
     pub fn width_extend(&mut self, rhs: &Size, respect_baseline: bool) {
         self.width += &rhs.width;
         if respect_baseline {
             let ascent = self.ascent_size().max(rhs.ascent_size());
+            // last baselines measured from the top after aligning the first ones
+            let last_ascent = |size: &Size| {
+                size.last_ascent()
+                    .map(|last_ascent| ascent - size.ascent_size() + last_ascent)
+            };
+            let last_ascent = last_ascent(self).max(last_ascent(rhs));
             self.depth = self.depth.max(rhs.depth);
             self.height = (self.depth().unwrap_or_default() + ascent).into();
+            self.last_depth = last_ascent.map(|last_ascent| self.base_height() - last_ascent);
+            self.drop_equal_last_depth();
         } else {
             self.height = self.height.max_of(&rhs.height);
         }
@@ -244,8 +338,22 @@ impl Size {
     pub fn height_extend(&mut self, rhs: &Size, respect_baseline: bool) {
         self.width = self.width.max_of(&rhs.width);
         self.height += &rhs.height;
-        if respect_baseline && self.depth.is_none() {
-            self.depth = rhs.depth;
+        if respect_baseline {
+            // the last baseline is the one of the lowest size having any
+            self.last_depth = rhs
+                .last_depth()
+                .or_else(|| self.last_depth().map(|depth| depth + rhs.base_height()));
+            if self.depth.is_none() {
+                self.depth = rhs.depth;
+            }
+            self.drop_equal_last_depth();
+        }
+    }
+
+    // A single baseline keeps just the depth.
+    fn drop_equal_last_depth(&mut self) {
+        if self.last_depth == self.depth {
+            self.last_depth = None;
         }
     }
 }
@@ -337,6 +445,16 @@ impl Quad {
         self
     }
 
+    // Left and right swapped.
+    pub fn mirrored(&self) -> Self {
+        Self {
+            top: self.top,
+            left: self.right,
+            bottom: self.bottom,
+            right: self.left,
+        }
+    }
+
     pub fn top(&self) -> Option<Unit> {
         self.top
     }
@@ -400,6 +518,9 @@ impl Quad {
                 if let Some(depth) = &mut size.depth {
                     *depth -= self.bottom_size();
                 }
+                if let Some(depth) = &mut size.last_depth {
+                    *depth -= self.bottom_size();
+                }
             }
         }
     }
@@ -423,11 +544,274 @@ impl Quad {
                 if let Some(depth) = &mut size.depth {
                     *depth += self.bottom_size();
                 }
+                if let Some(depth) = &mut size.last_depth {
+                    *depth += self.bottom_size();
+                }
             }
         }
     }
 }
 
+impl Add<&Quad> for &Quad {
+    type Output = Quad;
+
+    fn add(self, rhs: &Quad) -> Self::Output {
+        let add = |l: Option<Unit>, r: Option<Unit>| match (l, r) {
+            (Some(l), Some(r)) => Some(l + r),
+            (l, r) => l.or(r),
+        };
+        Quad {
+            top: add(self.top, rhs.top),
+            left: add(self.left, rhs.left),
+            bottom: add(self.bottom, rhs.bottom),
+            right: add(self.right, rhs.right),
+        }
+    }
+}
+
+// Room taken from the start and the end of lines, whose cross offset lies within top..bottom.
+// Floats of a parent indent lines of its children, which flow around them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Indent {
+    pub top: Unit,
+    pub bottom: Unit,
+    pub start: Unit,
+    pub end: Unit,
+}
+
+impl Indent {
+    // The widest start and end of indents overlapping a line at the offset.
+    pub fn at(indents: &[Indent], offset: Unit) -> (Unit, Unit) {
+        indents
+            .iter()
+            .filter(|indent| indent.top <= offset && offset < indent.bottom)
+            .fold((Unit::zero(), Unit::zero()), |(start, end), indent| {
+                (start.max(indent.start), end.max(indent.end))
+            })
+    }
+
+    // The same indent seen from inside of the insets.
+    pub fn narrowed(&self, insets: &Quad) -> Self {
+        Self {
+            top: self.top - insets.top_size(),
+            bottom: self.bottom - insets.top_size(),
+            start: (self.start - insets.left_size()).max(Unit::zero()),
+            end: (self.end - insets.right_size()).max(Unit::zero()),
+        }
+    }
+}
+
+// Affine transform of offsets, x' = a * x + c * y + e and y' = b * x + d * y + f.
+// With y growing downwards, positive rotation turns clockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: Unit,
+    pub f: Unit,
+}
+
+impl Affine {
+    pub const fn identity() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: Unit::zero(),
+            f: Unit::zero(),
+        }
+    }
+
+    pub fn translation(x: impl Into<Unit>, y: impl Into<Unit>) -> Self {
+        Self {
+            e: x.into(),
+            f: y.into(),
+            ..Self::identity()
+        }
+    }
+
+    pub fn scaling(x: f64, y: f64) -> Self {
+        Self {
+            a: x,
+            d: y,
+            ..Self::identity()
+        }
+    }
+
+    pub fn rotation(degrees: f64) -> Self {
+        // quarter turns are exact
+        let (sin, cos) = match degrees.rem_euclid(360.0) {
+            0.0 => (0.0, 1.0),
+            90.0 => (1.0, 0.0),
+            180.0 => (0.0, -1.0),
+            270.0 => (-1.0, 0.0),
+            _ => degrees.to_radians().sin_cos(),
+        };
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::identity()
+        }
+    }
+
+    // The transform applying self first and the next one after.
+    pub fn then(&self, next: &Affine) -> Self {
+        Self {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e: self.e * next.a + self.f * next.c + next.e,
+            f: self.e * next.b + self.f * next.d + next.f,
+        }
+    }
+
+    pub fn linear(&self) -> Self {
+        Self {
+            e: Unit::zero(),
+            f: Unit::zero(),
+            ..*self
+        }
+    }
+
+    pub fn translated(&self) -> Offset {
+        Offset::new(self.e, self.f)
+    }
+
+    pub fn apply(&self, offset: &Offset) -> Offset {
+        Offset::new(
+            offset.x * self.a + offset.y * self.c + self.e,
+            offset.x * self.b + offset.y * self.d + self.f,
+        )
+    }
+
+    // Only scales, mirrors or turns by quarters, i.e. axis aligned boxes stay axis aligned.
+    pub fn is_axis_aligned(&self) -> bool {
+        let zero = |value: f64| value.abs() < 1e-9;
+        (zero(self.b) && zero(self.c)) || (zero(self.a) && zero(self.d))
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant == 0.0 {
+            return None;
+        }
+        let linear = Self {
+            a: self.d / determinant,
+            b: -self.b / determinant,
+            c: -self.c / determinant,
+            d: self.a / determinant,
+            ..Self::identity()
+        };
+        let translation = linear.apply(&Offset::new(self.e, self.f));
+        Some(Self {
+            e: Unit::zero() - translation.x,
+            f: Unit::zero() - translation.y,
+            ..linear
+        })
+    }
+
+    // Top left corner and size of the box bounding the transformed rectangle.
+    pub fn bounds(&self, offset: &Offset, size: &Size) -> (Offset, Size) {
+        let corners = [
+            offset.clone(),
+            Offset::new(offset.x + size.base_width(), offset.y),
+            Offset::new(offset.x, offset.y + size.base_height()),
+            offset + size,
+        ]
+        .map(|corner| self.apply(&corner));
+
+        let min_x = corners.iter().map(|corner| corner.x).min().unwrap();
+        let min_y = corners.iter().map(|corner| corner.y).min().unwrap();
+        let max_x = corners.iter().map(|corner| corner.x).max().unwrap();
+        let max_y = corners.iter().map(|corner| corner.y).max().unwrap();
+
+        (
+            Offset::new(min_x, min_y),
+            Size::fixed(max_x - min_x, max_y - min_y),
+        )
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// Quad of calc expressions, resolved against parent width and font size into Quad.
+#[derive(Debug, Clone)]
+pub struct CalcQuad {
+    top: Option<Calc>,
+    left: Option<Calc>,
+    bottom: Option<Calc>,
+    right: Option<Calc>,
+}
+
+impl CalcQuad {
+    pub const fn empty() -> Self {
+        Self {
+            top: None,
+            left: None,
+            bottom: None,
+            right: None,
+        }
+    }
+
+    pub fn square(calc: Calc) -> Self {
+        Self {
+            top: Some(calc),
+            left: Some(calc),
+            bottom: Some(calc),
+            right: Some(calc),
+        }
+    }
+
+    pub fn h_v(horizontal: Calc, vertical: Calc) -> Self {
+        Self {
+            top: Some(vertical),
+            left: Some(horizontal),
+            bottom: Some(vertical),
+            right: Some(horizontal),
+        }
+    }
+
+    pub fn with_top(mut self, top: Calc) -> Self {
+        self.top = Some(top);
+        self
+    }
+
+    pub fn with_left(mut self, left: Calc) -> Self {
+        self.left = Some(left);
+        self
+    }
+
+    pub fn with_bottom(mut self, bottom: Calc) -> Self {
+        self.bottom = Some(bottom);
+        self
+    }
+
+    pub fn with_right(mut self, right: Calc) -> Self {
+        self.right = Some(right);
+        self
+    }
+
+    pub fn resolve(&self, parent: Unit, font_size: Unit) -> Quad {
+        let resolve = |calc: Option<Calc>| calc.map(|calc| calc.resolve(parent, font_size));
+        Quad {
+            top: resolve(self.top),
+            left: resolve(self.left),
+            bottom: resolve(self.bottom),
+            right: resolve(self.right),
+        }
+    }
+}
+
 impl From<&Quad> for Quad {
     fn from(quad: &Quad) -> Self {
         quad.clone()
@@ -538,4 +922,31 @@ mod tests {
         assert_eq!(24, size.base_height().0);
         assert_eq!(21, size.ascent_size().0);
     }
+
+    #[test]
+    fn it_extends_last_baseline() {
+        let size1 = Size::fixed_depth(10, 12, 2);
+        let size2 = Size::fixed_depth(20, 12, 3);
+
+        let mut size = size1.clone();
+        size.height_extend(&size2, true);
+        assert_eq!(2, size.depth().unwrap().0);
+        assert_eq!(3, size.last_depth().unwrap().0);
+        assert_eq!(21, size.last_ascent().unwrap().0);
+
+        // last baseline keeps below a size without any
+        let mut size = size1.clone();
+        size.height_extend(&Size::fixed(20, 5), true);
+        assert_eq!(7, size.last_depth().unwrap().0);
+
+        // first baselines align, the lowest last baseline wins
+        let mut lines = Size::fixed_depth(10, 30, 22);
+        lines.set_last_depth(Some(2));
+        let mut line = Size::fixed_depth(10, 10, 2);
+        line.width_extend(&lines, true);
+        assert_eq!(30, line.base_height().0);
+        assert_eq!(22, line.depth().unwrap().0);
+        assert_eq!(2, line.last_depth().unwrap().0);
+        assert_eq!(2, line.last_baseline().depth().unwrap().0);
+    }
 }
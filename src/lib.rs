@@ -27,6 +27,8 @@ pub use font::*;
 #[macro_use]
 mod layout;
 #[cfg(feature = "layout")]
+pub use self::layout::dimension::{Calc, Dim};
+#[cfg(feature = "layout")]
 pub use self::layout::*;
 
 #[cfg(feature = "layout")]
@@ -40,6 +42,16 @@ mod order_decorator;
 #[cfg(feature = "layout")]
 pub use self::order_decorator::*;
 
+#[cfg(feature = "layout")]
+mod page_template;
+#[cfg(feature = "layout")]
+pub use self::page_template::*;
+
+#[cfg(feature = "layout")]
+mod pagination;
+#[cfg(feature = "layout")]
+pub use self::pagination::*;
+
 #[cfg(feature = "layout")]
 #[macro_use]
 mod script;
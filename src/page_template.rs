@@ -0,0 +1,445 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    Error, FieldKind, Heading, Layout, LayoutBox, MarkerOnPage, MeasureContext, NewPageOptions,
+    Page, PageBreak, Style, TextPosition, hbox, hfill, hspace,
+    pagination::{lay_out_part, leading_page_break, paginate_in_frames},
+    position::{Offset, Size},
+    reference, text,
+    unit::Unit,
+    vbox,
+};
+
+// Layout passes until page numbers of targets do not change any more.
+const MAX_PASSES: usize = 5;
+
+type Factory = Box<dyn Fn() -> Box<dyn Layout> + Send + Sync>;
+
+// Heights of headers and footers by numbers of pages, which their frames reserve.
+type PartHeights = BTreeMap<usize, (Unit, Unit)>;
+
+// Header and footer of a page, created anew for every page.
+#[derive(Default)]
+pub struct PageTemplate {
+    header: Option<Factory>,
+    footer: Option<Factory>,
+}
+
+impl PageTemplate {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn header<L: Layout + 'static>(
+        mut self,
+        header: impl Fn() -> L + Send + Sync + 'static,
+    ) -> Self {
+        self.header = Some(Box::new(move || Box::new(header())));
+        self
+    }
+
+    pub fn footer<L: Layout + 'static>(
+        mut self,
+        footer: impl Fn() -> L + Send + Sync + 'static,
+    ) -> Self {
+        self.footer = Some(Box::new(move || Box::new(footer())));
+        self
+    }
+}
+
+// Values of fields of a page.
+#[derive(Debug, Clone, Default)]
+pub struct PageFields {
+    pub number: usize,
+    pub count: usize,
+    pub section: Option<String>,
+    // Texts of markers by their names.
+    pub first_markers: BTreeMap<String, String>,
+    pub last_markers: BTreeMap<String, String>,
+}
+
+impl PageFields {
+    pub fn value(&self, kind: &FieldKind) -> Option<String> {
+        match kind {
+            FieldKind::PageNumber => Some(self.number.to_string()),
+            FieldKind::PageCount => Some(self.count.to_string()),
+            FieldKind::SectionTitle => self.section.clone(),
+            FieldKind::TargetPage(_) | FieldKind::TargetLabel(_) => None,
+            FieldKind::Marker(name, MarkerOnPage::First) => self.first_markers.get(name).cloned(),
+            FieldKind::Marker(name, MarkerOnPage::Last) => self.last_markers.get(name).cloned(),
+        }
+    }
+}
+
+// Target resolved to the page it is laid out on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTarget {
+    pub id: String,
+    pub page: usize,
+    pub label: Option<String>,
+    pub heading: Option<Heading>,
+}
+
+// Targets of pages in order of the flow, the first one of an id wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Targets {
+    resolved: Vec<ResolvedTarget>,
+}
+
+impl Targets {
+    pub fn new(pages: &[Page]) -> Self {
+        let mut resolved = vec![];
+        for page in pages {
+            page_targets(page.content(), page.number(), &mut resolved);
+        }
+        Self { resolved }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ResolvedTarget> {
+        self.resolved.iter().find(|target| target.id == id)
+    }
+
+    pub fn headings(&self) -> impl Iterator<Item = &ResolvedTarget> {
+        self.resolved
+            .iter()
+            .filter(|target| target.heading.is_some())
+    }
+
+    // Label of a target without one is its page number.
+    pub fn value(&self, kind: &FieldKind) -> Option<String> {
+        match kind {
+            FieldKind::TargetPage(id) => self.get(id).map(|target| target.page.to_string()),
+            FieldKind::TargetLabel(id) => self
+                .get(id)
+                .map(|target| target.label.clone().unwrap_or(target.page.to_string())),
+            _ => None,
+        }
+    }
+
+    // Table of contents with a line per heading, indented by its level, and its page number.
+    pub fn toc(&self, indent: impl Into<Unit>) -> LayoutBox {
+        let indent = indent.into();
+        self.headings().fold(vbox(), |toc, target| {
+            let heading = target.heading.as_ref().unwrap();
+            toc.child(
+                hbox()
+                    .child(hspace(indent * heading.level.saturating_sub(1)))
+                    .child(text(&heading.title))
+                    .child(hfill(1))
+                    .child(reference(&target.id)),
+            )
+        })
+    }
+}
+
+// Measure context resolving fields of a page, if any, and references to targets.
+struct FieldContext<'a> {
+    inner: &'a mut dyn MeasureContext,
+    fields: Option<&'a PageFields>,
+    targets: &'a Targets,
+}
+
+impl MeasureContext for FieldContext<'_> {
+    fn style(&self) -> &Style {
+        self.inner.style()
+    }
+
+    fn typeset(&mut self, style: &Style, text: &str) -> Result<TextPosition, Error> {
+        self.inner.typeset(style, text)
+    }
+
+    fn field(&self, kind: &FieldKind) -> Option<String> {
+        self.targets
+            .value(kind)
+            .or_else(|| self.fields?.value(kind))
+    }
+}
+
+// Templates of pages in the area inside page margins. The first page, odd and even pages may
+// have their own templates, others use the default one. Content flows between the header and
+// the footer of a page.
+pub struct PageTemplates {
+    // size and margins of pages
+    page: NewPageOptions,
+    default: PageTemplate,
+    first: Option<PageTemplate>,
+    odd: Option<PageTemplate>,
+    even: Option<PageTemplate>,
+}
+
+impl PageTemplates {
+    pub fn new(page: NewPageOptions, default: PageTemplate) -> Self {
+        Self {
+            page,
+            default,
+            first: None,
+            odd: None,
+            even: None,
+        }
+    }
+
+    pub fn first(mut self, template: PageTemplate) -> Self {
+        self.first = Some(template);
+        self
+    }
+
+    pub fn odd(mut self, template: PageTemplate) -> Self {
+        self.odd = Some(template);
+        self
+    }
+
+    pub fn even(mut self, template: PageTemplate) -> Self {
+        self.even = Some(template);
+        self
+    }
+
+    pub fn template(&self, number: usize) -> &PageTemplate {
+        let own = match number {
+            1 => self.first.as_ref().or(self.odd.as_ref()),
+            number if number % 2 == 1 => self.odd.as_ref(),
+            _ => self.even.as_ref(),
+        };
+        own.unwrap_or(&self.default)
+    }
+
+    // Area inside margins of the page, mirrored margins swap their sides on even pages.
+    // Options of the page break starting the page override the size and margins of pages.
+    fn area(&self, number: usize, options: Option<&NewPageOptions>) -> (Offset, Size) {
+        let mut offset = Offset::zero();
+        let mut size = options
+            .and_then(|options| options.size.clone())
+            .or_else(|| self.page.size.clone())
+            .unwrap_or_else(Size::zero);
+        let margin = options
+            .and_then(|options| options.page_margin(number))
+            .or_else(|| self.page.page_margin(number));
+        if let Some(margin) = margin {
+            margin.narrow(Some(&mut offset), Some(&mut size));
+        }
+        (offset, size)
+    }
+
+    // Lays out the layout into pages, then headers and footers of pages with their fields.
+    // Heights of headers and footers are measured before pagination with the page count unknown,
+    // parts taller with fields of their pages are clamped to the reserved heights.
+    pub fn paginate(
+        &self,
+        ctx: &mut dyn MeasureContext,
+        layout: Box<dyn Layout>,
+    ) -> Result<Vec<Page>, Error> {
+        let (pages, _) =
+            self.paginate_resolving(ctx, layout, &Targets::default(), &PartHeights::new())?;
+        Ok(pages)
+    }
+
+    // Paginates the content created for targets of the previous pass, so that references and
+    // the table of contents show their pages, until targets stay on their pages and headers
+    // and footers fit the heights reserved for them.
+    pub fn paginate_with_targets(
+        &self,
+        ctx: &mut dyn MeasureContext,
+        content: impl Fn(&Targets) -> Box<dyn Layout>,
+    ) -> Result<Vec<Page>, Error> {
+        let mut targets = Targets::default();
+        let mut heights = PartHeights::new();
+        let mut passes = 0;
+        loop {
+            let (pages, measured) =
+                self.paginate_resolving(ctx, content(&targets), &targets, &heights)?;
+            let resolved = Targets::new(&pages);
+            passes += 1;
+            if (resolved == targets && measured.is_none()) || passes == MAX_PASSES {
+                return Ok(pages);
+            }
+            targets = resolved;
+            heights = measured.unwrap_or(heights);
+        }
+    }
+
+    // Pages and heights of headers and footers measured with fields of the pages, if they
+    // differ from the reserved ones. Pages without reserved heights use the estimated ones.
+    fn paginate_resolving(
+        &self,
+        ctx: &mut dyn MeasureContext,
+        mut layout: Box<dyn Layout>,
+        targets: &Targets,
+        reserved: &PartHeights,
+    ) -> Result<(Vec<Page>, Option<PartHeights>), Error> {
+        let mut heights = vec![];
+        for number in 1..=3 {
+            let fields = PageFields {
+                number,
+                count: number,
+                ..Default::default()
+            };
+            let template = self.template(number);
+            let room = self.area(number, None).1;
+            let header = self.part(ctx, template.header.as_ref(), &fields, targets, &room)?;
+            let footer = self.part(ctx, template.footer.as_ref(), &fields, targets, &room)?;
+            heights.push((part_height(&header), part_height(&footer)));
+        }
+        let reserved = |number: usize| match reserved.get(&number) {
+            Some(heights) => *heights,
+            // the first page, then odd and even ones
+            None if number == 1 => heights[0],
+            None if number % 2 == 1 => heights[2],
+            None => heights[1],
+        };
+        let frame = |number: usize, options: Option<&NewPageOptions>| {
+            let (header, footer) = reserved(number);
+            let (area_offset, area_size) = self.area(number, options);
+            let offset = Offset::new(area_offset.x, area_offset.y + header);
+            let height = area_size.base_height() - header - footer;
+            (offset, Size::fixed(area_size.base_width(), height))
+        };
+
+        let options = leading_page_break(layout.as_ref()).and_then(PageBreak::options_ref);
+        let (offset, size) = frame(1, options);
+        let mut pages = {
+            let ctx = &mut FieldContext {
+                inner: ctx,
+                fields: None,
+                targets,
+            };
+            lay_out_part(ctx, layout.as_mut(), &offset, &size)?;
+            paginate_in_frames(ctx, layout, frame)?
+        };
+
+        let count = pages.len();
+        let mut measured = PartHeights::new();
+        let mut section = None;
+        let mut last_markers = BTreeMap::new();
+        for page in pages.iter_mut() {
+            // the first section starting on the page, or the one continuing
+            let mut titles = vec![];
+            section_titles(page.content(), &mut titles);
+            // the first and last markers on the page, or the ones continuing
+            let mut markers = vec![];
+            page_markers(page.content(), &mut markers);
+            let mut first_markers = last_markers.clone();
+            for (name, text) in markers.iter().rev() {
+                first_markers.insert(name.clone(), text.clone());
+            }
+            last_markers.extend(markers);
+            let fields = PageFields {
+                number: page.number(),
+                count,
+                section: titles.first().or(section.as_ref()).cloned(),
+                first_markers,
+                last_markers: last_markers.clone(),
+            };
+            section = titles.pop().or(section);
+            if page.is_blank() {
+                continue;
+            }
+
+            let (area_offset, area_size) = self.area(page.number(), page.options());
+            let width = area_size.base_width();
+            let template = self.template(page.number());
+            let mut header =
+                self.part(ctx, template.header.as_ref(), &fields, targets, &area_size)?;
+            let mut footer =
+                self.part(ctx, template.footer.as_ref(), &fields, targets, &area_size)?;
+            let heights = (part_height(&header), part_height(&footer));
+            measured.insert(page.number(), heights);
+            // parts taller than the frame of the page reserves do not overlap the content
+            let (header_reserved, footer_reserved) = reserved(page.number());
+            let header_height = clamp_height(&mut header, header_reserved);
+            let footer_height = clamp_height(&mut footer, footer_reserved);
+
+            let ctx = &mut FieldContext {
+                inner: ctx,
+                fields: Some(&fields),
+                targets,
+            };
+            if let Some(header) = header.as_mut() {
+                let size = Size::fixed(width, header_height);
+                header.lay_out(ctx, area_offset.clone(), size)?;
+            }
+            if let Some(footer) = footer.as_mut() {
+                let bottom = area_offset.y + area_size.base_height();
+                let offset = Offset::new(area_offset.x, bottom - footer_height);
+                footer.lay_out(ctx, offset, Size::fixed(width, footer_height))?;
+            }
+            page.header = header;
+            page.footer = footer;
+        }
+
+        let changed = measured
+            .iter()
+            .any(|(number, heights)| *heights != reserved(*number));
+        Ok((pages, changed.then_some(measured)))
+    }
+
+    // Header or footer measured with fields of the page.
+    fn part(
+        &self,
+        ctx: &mut dyn MeasureContext,
+        factory: Option<&Factory>,
+        fields: &PageFields,
+        targets: &Targets,
+        room: &Size,
+    ) -> Result<Option<Box<dyn Layout>>, Error> {
+        let Some(factory) = factory else {
+            return Ok(None);
+        };
+        let mut part = factory();
+        let ctx = &mut FieldContext {
+            inner: ctx,
+            fields: Some(fields),
+            targets,
+        };
+        part.measure(ctx, room.clone())?;
+        Ok(Some(part))
+    }
+}
+
+fn part_height(part: &Option<Box<dyn Layout>>) -> Unit {
+    part.as_ref()
+        .and_then(|part| part.size_after_wrap_ref())
+        .map(|size| size.base_height())
+        .unwrap_or_default()
+}
+
+fn clamp_height(part: &mut Option<Box<dyn Layout>>, height: Unit) -> Unit {
+    if part_height(part) > height
+        && let Some(part) = part.as_mut()
+    {
+        part.size_mut().set_base_height(height);
+    }
+    part_height(part)
+}
+
+// Names and texts of markers in the layout in order of the flow.
+fn page_markers(layout: &dyn Layout, markers: &mut Vec<(String, String)>) {
+    if let Some(marker) = layout.marker() {
+        markers.push((marker.name().to_string(), marker.text().to_string()));
+    }
+    for child in layout.iter() {
+        page_markers(child.as_ref(), markers);
+    }
+}
+
+// Titles of sections in the layout in order of the flow.
+fn section_titles(layout: &dyn Layout, titles: &mut Vec<String>) {
+    if let Some(title) = layout.section_title() {
+        titles.push(title.to_string());
+    }
+    for child in layout.iter() {
+        section_titles(child.as_ref(), titles);
+    }
+}
+
+fn page_targets(layout: &dyn Layout, page: usize, resolved: &mut Vec<ResolvedTarget>) {
+    if let Some(target) = layout.target() {
+        resolved.push(ResolvedTarget {
+            id: target.id().to_string(),
+            page,
+            label: target.label_ref().map(|label| label.to_string()),
+            heading: target.heading_ref().cloned(),
+        });
+    }
+    for child in layout.iter() {
+        page_targets(child.as_ref(), page, resolved);
+    }
+}
@@ -0,0 +1,365 @@
+use image::DynamicImage;
+
+use crate::{
+    Axis, BreakBetween, Error, FieldKind, Layout, LayoutBox, MeasureContext, NewPageOptions,
+    PageBreak, Position, RenderContext, Stroke, Style, TextPosition,
+    position::{Affine, Offset, Size},
+    unit::Unit,
+};
+
+// Part of the layout laid out on its own page.
+pub struct Page {
+    number: usize,
+    content: Box<dyn Layout>,
+    blank: bool,
+    options: Option<NewPageOptions>,
+    footnotes: Option<Box<dyn Layout>>,
+    pub(crate) header: Option<Box<dyn Layout>>,
+    pub(crate) footer: Option<Box<dyn Layout>>,
+}
+
+impl Page {
+    fn new(number: usize, content: Box<dyn Layout>) -> Self {
+        let options = leading_page_break(content.as_ref())
+            .and_then(|page_break| page_break.options_ref())
+            .cloned();
+        Self {
+            number,
+            content,
+            blank: false,
+            options,
+            footnotes: None,
+            header: None,
+            footer: None,
+        }
+    }
+
+    // Page left empty, so that a break continues on a page of the requested side.
+    fn blank(number: usize, options: Option<NewPageOptions>) -> Self {
+        Self {
+            number,
+            content: Box::new(LayoutBox::new(Axis::Vertical)),
+            blank: true,
+            options,
+            footnotes: None,
+            header: None,
+            footer: None,
+        }
+    }
+
+    // Numbered from one, odd pages are right ones.
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.blank
+    }
+
+    // Options of the page break starting the page, which the page is set up with.
+    pub fn options(&self) -> Option<&NewPageOptions> {
+        self.options.as_ref()
+    }
+
+    pub fn content(&self) -> &dyn Layout {
+        self.content.as_ref()
+    }
+
+    pub fn into_content(self) -> Box<dyn Layout> {
+        self.content
+    }
+
+    // Bodies of footnotes referenced on the page, at the bottom of the content.
+    pub fn footnotes(&self) -> Option<&dyn Layout> {
+        self.footnotes.as_deref()
+    }
+
+    // Laid out by the page template, see PageTemplates.
+    pub fn header(&self) -> Option<&dyn Layout> {
+        self.header.as_deref()
+    }
+
+    pub fn footer(&self) -> Option<&dyn Layout> {
+        self.footer.as_deref()
+    }
+
+    // Renders on the current page, the caller starts pages with their options. Page breaks
+    // in the content do not start pages, as the content already broke there.
+    pub fn render(&self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        let ctx = &mut OnPage { inner: ctx };
+        if let Some(header) = &self.header {
+            header.render(ctx)?;
+        }
+        self.content.render(ctx)?;
+        if let Some(footnotes) = &self.footnotes {
+            footnotes.render(ctx)?;
+        }
+        if let Some(footer) = &self.footer {
+            footer.render(ctx)?;
+        }
+        Ok(())
+    }
+}
+
+// Splits the laid out layout into pages of the content height at its breaks, e.g. between
+// stacked children or lines of paragraphs. Every part is laid out again at the offset of the
+// layout, so that the offsets are relative to its page. Parts without any break overflow.
+// Forced breaks split pages earlier, blank pages are inserted for breaks to a page side.
+pub fn paginate(
+    ctx: &mut dyn MeasureContext,
+    layout: Box<dyn Layout>,
+    height: impl Into<Unit>,
+) -> Result<Vec<Page>, Error> {
+    let size = Size::fixed(layout.size().base_width(), height.into());
+    let offset = layout.offset().clone();
+    paginate_in_frames(ctx, layout, |_, _| (offset.clone(), size.clone()))
+}
+
+// Pagination into frames of pages by their numbers and options of page breaks starting them,
+// i.e. content offsets and sizes. The layout is laid out in the frame of the first page.
+// Bodies of footnotes stack at the bottom of the frame, the part not fitting continues
+// on the next page.
+pub(crate) fn paginate_in_frames(
+    ctx: &mut dyn MeasureContext,
+    mut layout: Box<dyn Layout>,
+    frame: impl Fn(usize, Option<&NewPageOptions>) -> (Offset, Size),
+) -> Result<Vec<Page>, Error> {
+    let options = leading_page_break(layout.as_ref()).and_then(PageBreak::options_ref);
+    let (mut offset, size) = frame(1, options);
+    let (mut width, mut height) = (size.base_width(), size.base_height());
+
+    let mut pages = vec![];
+    let mut carried: Option<Box<dyn Layout>> = None;
+    loop {
+        // continued footnotes leave at least half of the page to the content
+        let carried_height = match carried.as_mut() {
+            Some(part) => laid_out_height(ctx, part.as_mut(), &offset, width, height)?,
+            None => Unit::zero(),
+        };
+        let limit = height - carried_height.min(height / 2.0);
+
+        let mut notes = vec![];
+        let layout_top = layout.offset().y;
+        for footnote in layout.footnotes_mut() {
+            let top = footnote.offset().y - layout_top;
+            if let Some(body) = footnote.body_mut() {
+                notes.push((
+                    top,
+                    laid_out_height(ctx, body.as_mut(), &offset, width, height)?,
+                ));
+            }
+        }
+        let notes_height = |content: Unit| {
+            notes
+                .iter()
+                .filter(|(top, _)| *top < content)
+                .fold(Unit::zero(), |sum, (_, height)| sum + *height)
+        };
+
+        let used = layout
+            .size_after_lay_out()
+            .map(|size| size.base_height())
+            .unwrap_or_default();
+        let forced = layout
+            .forced_breaks()
+            .into_iter()
+            .filter(|(at, _)| *at > Unit::zero() && *at <= limit)
+            .min_by_key(|(at, _)| *at);
+        let mut breaks = layout
+            .breaks()
+            .into_iter()
+            .filter(|at| *at > Unit::zero())
+            .collect::<Vec<_>>();
+        breaks.sort_by(|a, b| b.cmp(a));
+        breaks.dedup();
+
+        // the farthest break, which the content and the footnotes above it fit at,
+        // or the farthest one fitting the content with the footnotes split,
+        // or the nearest one when none fits
+        let mut candidates = match forced {
+            Some((at, between)) => vec![(Some(at), between)],
+            None if used <= limit => vec![(None, BreakBetween::Auto)],
+            None => vec![],
+        };
+        let forced_at = forced.map(|(at, _)| at);
+        candidates.extend(
+            breaks
+                .iter()
+                .filter(|at| **at <= forced_at.unwrap_or(limit) && Some(**at) != forced_at)
+                .map(|at| (Some(*at), BreakBetween::Auto)),
+        );
+        let (at, between) = candidates
+            .iter()
+            .find(|(at, _)| {
+                let content = at.unwrap_or(used);
+                content + notes_height(content) <= limit
+            })
+            .or(candidates.first())
+            .copied()
+            .unwrap_or((breaks.last().copied(), BreakBetween::Auto));
+        let tail = at.and_then(|at| layout.split_off(at));
+        if tail.is_some() {
+            lay_out_part(ctx, layout.as_mut(), &offset, &Size::fixed(width, height))?;
+        }
+
+        let mut area = LayoutBox::new(Axis::Vertical);
+        if let Some(part) = carried.take() {
+            area = area.child_dyn(part);
+        }
+        for footnote in layout.footnotes_mut() {
+            if let Some(body) = footnote.take_body() {
+                area = area.child_dyn(body);
+            }
+        }
+        let mut page = Page::new(pages.len() + 1, layout);
+        if area.iter().next().is_some() {
+            let content = page
+                .content()
+                .size_after_lay_out()
+                .map(|size| size.base_height())
+                .unwrap_or_default();
+            let area_height = laid_out_height(ctx, &mut area, &offset, width, height)?;
+            if area_height > height - content {
+                carried = area.split_off(height - content);
+            }
+            let area_height = laid_out_height(ctx, &mut area, &offset, width, height)?;
+            let bottom = Offset::new(offset.x, offset.y + height - area_height);
+            lay_out_part(ctx, &mut area, &bottom, &Size::fixed(width, height))?;
+            page.footnotes = Some(Box::new(area));
+        }
+        pages.push(page);
+
+        let tail = match tail {
+            Some(tail) => tail,
+            None if carried.is_some() => Box::new(LayoutBox::new(Axis::Vertical)),
+            None => return Ok(pages),
+        };
+        let options = leading_page_break(tail.as_ref())
+            .and_then(PageBreak::options_ref)
+            .cloned();
+        if !between.fits_page(pages.len() + 1) {
+            pages.push(Page::blank(pages.len() + 1, options.clone()));
+        }
+
+        let size;
+        (offset, size) = frame(pages.len() + 1, options.as_ref());
+        (width, height) = (size.base_width(), size.base_height());
+        layout = tail;
+        lay_out_part(ctx, layout.as_mut(), &offset, &Size::fixed(width, height))?;
+    }
+}
+
+pub(crate) fn lay_out_part(
+    ctx: &mut dyn MeasureContext,
+    layout: &mut dyn Layout,
+    offset: &Offset,
+    room: &Size,
+) -> Result<(), Error> {
+    layout.measure(ctx, room.clone())?;
+    let height = layout
+        .size_after_wrap_ref()
+        .map(|size| size.base_height())
+        .unwrap_or_default();
+    layout.lay_out(ctx, offset.clone(), Size::fixed(room.base_width(), height))
+}
+
+// Page break ahead of the first child taking space, in the first children too.
+pub(crate) fn leading_page_break(layout: &dyn Layout) -> Option<&PageBreak> {
+    for child in layout.iter() {
+        if let Some(page_break) = child.page_break() {
+            return Some(page_break);
+        }
+        if child.size_after_wrap_ref().is_some() {
+            return leading_page_break(child.as_ref());
+        }
+    }
+    None
+}
+
+// Render context of a page ignoring page breaks.
+struct OnPage<'a> {
+    inner: &'a mut dyn RenderContext,
+}
+
+impl MeasureContext for OnPage<'_> {
+    fn style(&self) -> &Style {
+        self.inner.style()
+    }
+
+    fn typeset(&mut self, style: &Style, text: &str) -> Result<TextPosition, Error> {
+        self.inner.typeset(style, text)
+    }
+
+    fn field(&self, kind: &FieldKind) -> Option<String> {
+        self.inner.field(kind)
+    }
+}
+
+impl RenderContext for OnPage<'_> {
+    fn debug_frame(&mut self, offset: &Offset, size: &Size) {
+        self.inner.debug_frame(offset, size);
+    }
+
+    fn check_page_break(&mut self, offset: Unit, height: Unit, reserve_height: bool) -> bool {
+        self.inner.check_page_break(offset, height, reserve_height)
+    }
+
+    fn release_page_break_reservation(&mut self) {
+        self.inner.release_page_break_reservation();
+    }
+
+    fn new_page(&mut self, _: Option<NewPageOptions>) {}
+
+    fn page_number(&self) -> Option<usize> {
+        self.inner.page_number()
+    }
+
+    fn push_transform(&mut self, transform: &Affine) {
+        self.inner.push_transform(transform);
+    }
+
+    fn pop_transform(&mut self) {
+        self.inner.pop_transform();
+    }
+
+    fn push_clip(&mut self, offset: &Offset, size: &Size) {
+        self.inner.push_clip(offset, size);
+    }
+
+    fn pop_clip(&mut self) {
+        self.inner.pop_clip();
+    }
+
+    fn image(&mut self, from: &Offset, to: &Offset, image: DynamicImage) {
+        self.inner.image(from, to, image);
+    }
+
+    fn line(&mut self, from: &Offset, to: &Offset, stroke: &Stroke) {
+        self.inner.line(from, to, stroke);
+    }
+
+    fn text(
+        &mut self,
+        offset: &Offset,
+        style: &Style,
+        text: &TextPosition,
+        offset_is_baseline: bool,
+    ) {
+        self.inner.text(offset, style, text, offset_is_baseline);
+    }
+}
+
+// Height of the layout laid out at the offset in the room.
+fn laid_out_height(
+    ctx: &mut dyn MeasureContext,
+    layout: &mut dyn Layout,
+    offset: &Offset,
+    width: Unit,
+    height: Unit,
+) -> Result<Unit, Error> {
+    lay_out_part(ctx, layout, offset, &Size::fixed(width, height))?;
+    Ok(layout
+        .size_after_lay_out()
+        .map(|size| size.base_height())
+        .unwrap_or_default())
+}
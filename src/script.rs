@@ -1,11 +1,12 @@
 use smol_str::SmolStr;
 
 use crate::{
-    AlignItems, Border, Layout, Rgba, StyleBuilder, bbox, hbox, hfill, hfilling, hspace, hwrap,
+    AlignItems, Basis, Border, BreakBetween, BreakInside, Clear, Float, Layout, NewPageOptions,
+    Overflow, Rgba, StyleBuilder, bbox, hbox, hfill, hfilling, hspace, hwrap,
     position::{Offset, Quad},
     text,
     unit::{Fill, Pt, Unit},
-    vbox, vbreak, vfill, vfilling, vspace, vwrap,
+    vbox, vbreak_even, vbreak_odd, vbreak_with, vfill, vfilling, vspace, vwrap,
 };
 
 pub enum Element {
@@ -24,7 +25,9 @@ pub enum Element {
     Vfill(Fill),
     VfillShrink(Fill),
     Vwrap,
-    Vbreak,
+    Vbreak(Option<NewPageOptions>),
+    VbreakOdd(Option<NewPageOptions>),
+    VbreakEven(Option<NewPageOptions>),
 
     Text(Vec<Format>, String),
 }
@@ -39,7 +42,18 @@ pub enum Format {
     Points(Pt),
     Scaling(Fill),
     Padding(Quad),
+    Margin(Quad),
     Border(Border),
+    Basis(Basis),
+    Float(Float),
+    Clear(Clear),
+    Overflow(Overflow),
+    Widows(usize),
+    Orphans(usize),
+    BreakBefore(BreakBetween),
+    BreakAfter(BreakBetween),
+    BreakInside(BreakInside),
+    KeepWithNext(bool),
     Grow(Fill),
     Shrink(Fill),
     Wrap(bool),
@@ -111,7 +125,9 @@ pub fn lay_out(element: &Element) -> Box<dyn Layout> {
         Element::Vfill(fill) => Box::new(vfill(fill.clone())),
         Element::VfillShrink(fill) => Box::new(vfilling().grow(fill.clone()).shrink(fill.clone())),
         Element::Vwrap => Box::new(vwrap()),
-        Element::Vbreak => Box::new(vbreak()),
+        Element::Vbreak(options) => Box::new(vbreak_with(options.clone())),
+        Element::VbreakOdd(options) => Box::new(vbreak_odd(options.clone())),
+        Element::VbreakEven(options) => Box::new(vbreak_even(options.clone())),
 
         Element::Text(format, value) => {
             let mut text = text(value);
@@ -134,7 +150,18 @@ fn apply_format(layout: &mut dyn Layout, format: &[Format]) {
                 Format::Points(points) => style.with_font_size(points.clone()),
                 Format::Scaling(scaling) => style.with_font_scaling(scaling.clone()),
                 Format::Padding(padding) => style.with_padding(padding.clone()),
+                Format::Margin(margin) => style.with_margin(margin.clone()),
                 Format::Border(border) => style.with_border(border.clone()),
+                Format::Basis(basis) => style.with_basis(*basis),
+                Format::Float(float) => style.with_float(*float),
+                Format::Clear(clear) => style.with_clear(*clear),
+                Format::Overflow(overflow) => style.with_overflow(*overflow),
+                Format::Widows(lines) => style.with_widows(*lines),
+                Format::Orphans(lines) => style.with_orphans(*lines),
+                Format::BreakBefore(page_break) => style.with_break_before(*page_break),
+                Format::BreakAfter(page_break) => style.with_break_after(*page_break),
+                Format::BreakInside(page_break) => style.with_break_inside(*page_break),
+                Format::KeepWithNext(keep) => style.with_keep_with_next(*keep),
                 Format::Grow(grow) => style.with_grow(grow.clone()),
                 Format::Shrink(shrink) => style.with_shrink(shrink.clone()),
                 Format::Wrap(wrap) => style.with_wrap(*wrap),